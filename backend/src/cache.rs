@@ -0,0 +1,40 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+/// Small in-process TTL cache for JSON responses that are expensive to compute
+/// (storefront aggregates and the like). Entries are keyed by string so callers
+/// can invalidate whole groups with a shared prefix.
+#[derive(Clone, Default)]
+pub struct Cache {
+    entries: Arc<RwLock<HashMap<String, (Instant, serde_json::Value)>>>,
+}
+
+impl Cache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<serde_json::Value> {
+        let entries = self.entries.read().ok()?;
+        match entries.get(key) {
+            Some((expires_at, value)) if *expires_at > Instant::now() => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn set(&self, key: &str, value: serde_json::Value, ttl: Duration) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.retain(|_, (expires_at, _)| *expires_at > Instant::now());
+            entries.insert(key.to_string(), (Instant::now() + ttl, value));
+        }
+    }
+
+    pub fn invalidate_prefix(&self, prefix: &str) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.retain(|key, _| !key.starts_with(prefix));
+        }
+    }
+}
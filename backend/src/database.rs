@@ -1,8 +1,11 @@
 use sqlx::{postgres::PgPoolOptions, PgPool, Row};
 use std::time::Duration;
 
+use crate::cache::Cache;
+
 pub struct Database {
     pub pool: PgPool,
+    pub cache: Cache,
 }
 
 impl Database {
@@ -13,7 +16,10 @@ impl Database {
             .connect(database_url)
             .await?;
 
-        Ok(Database { pool })
+        Ok(Database {
+            pool,
+            cache: Cache::new(),
+        })
     }
 
     pub async fn run_migrations(&self) -> anyhow::Result<()> {
@@ -43,7 +49,7 @@ impl Database {
             r#"
             CREATE TABLE IF NOT EXISTS posts (
                 id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-                user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
                 title VARCHAR(255) NOT NULL,
                 content TEXT,
                 media_url TEXT,
@@ -153,41 +159,1030 @@ impl Database {
         .execute(&self.pool)
         .await?;
 
-        // Create indexes
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_posts_user_id ON posts(user_id)")
+        sqlx::query("ALTER TABLE products ADD COLUMN IF NOT EXISTS featured BOOLEAN NOT NULL DEFAULT FALSE")
             .execute(&self.pool)
             .await?;
 
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_products_user_id ON products(user_id)")
+        sqlx::query("ALTER TABLE products ADD COLUMN IF NOT EXISTS rating_average DOUBLE PRECISION")
             .execute(&self.pool)
             .await?;
 
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_subscriptions_user_id ON subscriptions(user_id)")
+        sqlx::query("ALTER TABLE products ADD COLUMN IF NOT EXISTS rating_count INTEGER NOT NULL DEFAULT 0")
             .execute(&self.pool)
             .await?;
 
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_subscriptions_creator_id ON subscriptions(creator_id)")
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS product_reviews (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+                user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                rating SMALLINT NOT NULL CHECK (rating BETWEEN 1 AND 5),
+                body TEXT,
+                creator_reply TEXT,
+                creator_replied_at TIMESTAMP WITH TIME ZONE,
+                is_hidden BOOLEAN NOT NULL DEFAULT FALSE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                UNIQUE (product_id, user_id)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("ALTER TABLE users ADD COLUMN IF NOT EXISTS role VARCHAR(20) NOT NULL DEFAULT 'user'")
             .execute(&self.pool)
             .await?;
 
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_campaigns_creator_id ON campaigns(creator_id)")
+        sqlx::query("ALTER TABLE purchases ADD COLUMN IF NOT EXISTS refunded_amount DECIMAL(10,2) NOT NULL DEFAULT 0")
             .execute(&self.pool)
             .await?;
 
-        println!("✅ Database migrations completed successfully!");
-        Ok(())
-    }
-}
+        sqlx::query("ALTER TABLE purchases ADD COLUMN IF NOT EXISTS access_revoked_at TIMESTAMP WITH TIME ZONE")
+            .execute(&self.pool)
+            .await?;
 
-impl Clone for Database {
-    fn clone(&self) -> Self {
-        Database {
-            pool: self.pool.clone(),
-        }
-    }
-}
+        sqlx::query("ALTER TABLE purchases ADD COLUMN IF NOT EXISTS disputed_at TIMESTAMP WITH TIME ZONE")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE subscriptions ADD COLUMN IF NOT EXISTS last_payment_intent_id VARCHAR(255)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE subscriptions ADD COLUMN IF NOT EXISTS last_charge_amount DOUBLE PRECISION")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE subscriptions ADD COLUMN IF NOT EXISTS last_charge_currency VARCHAR(3)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE subscriptions ADD COLUMN IF NOT EXISTS last_charged_at TIMESTAMP WITH TIME ZONE")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE subscriptions ADD COLUMN IF NOT EXISTS refunded_amount DOUBLE PRECISION NOT NULL DEFAULT 0")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE subscriptions ADD COLUMN IF NOT EXISTS disputed_at TIMESTAMP WITH TIME ZONE")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS refunds (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                target_type VARCHAR(50) NOT NULL,
+                target_id UUID NOT NULL,
+                amount DOUBLE PRECISION NOT NULL,
+                currency VARCHAR(3) DEFAULT 'USD',
+                reason TEXT,
+                status VARCHAR(50) NOT NULL,
+                stripe_refund_id VARCHAR(255),
+                failure_reason TEXT,
+                initiated_by VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS payment_disputes (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                stripe_dispute_id VARCHAR(255) UNIQUE NOT NULL,
+                target_type VARCHAR(50) NOT NULL,
+                target_id UUID NOT NULL,
+                amount DOUBLE PRECISION NOT NULL,
+                reason VARCHAR(100),
+                status VARCHAR(50) NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS payment_audit_log (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                target_type VARCHAR(50) NOT NULL,
+                target_id UUID NOT NULL,
+                action VARCHAR(50) NOT NULL,
+                from_status VARCHAR(50),
+                to_status VARCHAR(50),
+                amount DOUBLE PRECISION,
+                actor_id VARCHAR(255),
+                details TEXT,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS donations (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+                user_id VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL,
+                amount DOUBLE PRECISION NOT NULL CHECK (amount > 0),
+                currency VARCHAR(3) DEFAULT 'USD',
+                message TEXT,
+                is_anonymous BOOLEAN NOT NULL DEFAULT FALSE,
+                status VARCHAR(50) NOT NULL,
+                payment_method VARCHAR(50),
+                stripe_payment_intent_id VARCHAR(255),
+                refunded_amount DOUBLE PRECISION NOT NULL DEFAULT 0,
+                completed_at TIMESTAMP WITH TIME ZONE,
+                disputed_at TIMESTAMP WITH TIME ZONE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS published_at TIMESTAMP WITH TIME ZONE")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS closed_at TIMESTAMP WITH TIME ZONE")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS notifications (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                kind VARCHAR(50) NOT NULL,
+                title VARCHAR(255) NOT NULL,
+                message TEXT,
+                link TEXT,
+                actor_id VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL,
+                read_at TIMESTAMP WITH TIME ZONE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS campaign_reward_tiers (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+                title VARCHAR(255) NOT NULL,
+                description TEXT,
+                min_amount DOUBLE PRECISION NOT NULL CHECK (min_amount > 0),
+                quantity_limit INTEGER CHECK (quantity_limit > 0),
+                quantity_claimed INTEGER NOT NULL DEFAULT 0,
+                estimated_delivery DATE,
+                requires_shipping BOOLEAN NOT NULL DEFAULT FALSE,
+                position INTEGER NOT NULL DEFAULT 0,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                CHECK (quantity_limit IS NULL OR quantity_claimed <= quantity_limit)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("ALTER TABLE donations ADD COLUMN IF NOT EXISTS reward_tier_id UUID REFERENCES campaign_reward_tiers(id) ON DELETE SET NULL")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS reward_fulfillments (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                donation_id UUID UNIQUE NOT NULL REFERENCES donations(id) ON DELETE CASCADE,
+                reward_tier_id UUID NOT NULL REFERENCES campaign_reward_tiers(id) ON DELETE CASCADE,
+                status VARCHAR(50) NOT NULL,
+                shipping_name VARCHAR(255),
+                address_line1 VARCHAR(255),
+                address_line2 VARCHAR(255),
+                city VARCHAR(255),
+                region VARCHAR(255),
+                postal_code VARCHAR(50),
+                country VARCHAR(2),
+                tracking_number VARCHAR(255),
+                shipped_at TIMESTAMP WITH TIME ZONE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS funding_model VARCHAR(20) NOT NULL DEFAULT 'keep_it_all'")
+            .execute(&self.pool)
+            .await?;
+
+        // Totals and reward minimums are in this currency, so donations must match it
+        sqlx::query("ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'USD'")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE donations ADD COLUMN IF NOT EXISTS settled_at TIMESTAMP WITH TIME ZONE")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE donations ADD COLUMN IF NOT EXISTS settlement_error TEXT")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS campaign_stretch_goals (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+                title VARCHAR(255) NOT NULL,
+                description TEXT,
+                target_amount DOUBLE PRECISION NOT NULL CHECK (target_amount > 0),
+                unlocked_at TIMESTAMP WITH TIME ZONE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                UNIQUE(campaign_id, target_amount)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS stream_events (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                creator_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                kind VARCHAR(50) NOT NULL,
+                title VARCHAR(255) NOT NULL,
+                message TEXT,
+                amount DOUBLE PRECISION,
+                campaign_id UUID REFERENCES campaigns(id) ON DELETE CASCADE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS campaign_updates (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+                author_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                title VARCHAR(255) NOT NULL,
+                content TEXT NOT NULL,
+                image_url TEXT,
+                backers_only BOOLEAN NOT NULL DEFAULT FALSE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS comments (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                campaign_id UUID REFERENCES campaigns(id) ON DELETE CASCADE,
+                user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                parent_id UUID REFERENCES comments(id) ON DELETE CASCADE,
+                content TEXT,
+                is_pinned BOOLEAN NOT NULL DEFAULT FALSE,
+                edited_at TIMESTAMP WITH TIME ZONE,
+                deleted_at TIMESTAMP WITH TIME ZONE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Set while an admin features the campaign
+        sqlx::query("ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS featured_at TIMESTAMP WITH TIME ZONE")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS search_vector tsvector
+            GENERATED ALWAYS AS (
+                setweight(to_tsvector('english', COALESCE(title, '')), 'A') ||
+                setweight(to_tsvector('english', COALESCE(description, '')), 'B') ||
+                setweight(to_tsvector('english', COALESCE(story, '')), 'C')
+            ) STORED
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Comments can belong to a post instead of a campaign
+        sqlx::query("ALTER TABLE comments ADD COLUMN IF NOT EXISTS post_id UUID REFERENCES posts(id) ON DELETE CASCADE")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE comments ADD COLUMN IF NOT EXISTS like_count INTEGER NOT NULL DEFAULT 0")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE posts ADD COLUMN IF NOT EXISTS like_count INTEGER NOT NULL DEFAULT 0")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE posts ADD COLUMN IF NOT EXISTS comment_count INTEGER NOT NULL DEFAULT 0")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE posts ADD COLUMN IF NOT EXISTS comments_enabled BOOLEAN NOT NULL DEFAULT TRUE")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS post_likes (
+                post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+                user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                PRIMARY KEY (post_id, user_id)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS comment_likes (
+                comment_id UUID NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+                user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                PRIMARY KEY (comment_id, user_id)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS bookmarks (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                content_type VARCHAR(20) NOT NULL,
+                content_id VARCHAR(255) NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                UNIQUE(user_id, content_type, content_id)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS membership_tiers (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                creator_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                name VARCHAR(100) NOT NULL,
+                description TEXT,
+                price DOUBLE PRECISION NOT NULL CHECK (price >= 0),
+                currency VARCHAR(3) DEFAULT 'USD',
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("ALTER TABLE subscriptions ADD COLUMN IF NOT EXISTS tier_id UUID REFERENCES membership_tiers(id) ON DELETE SET NULL")
+            .execute(&self.pool)
+            .await?;
+
+        // Premium posts can require a minimum tier or a product purchase
+        sqlx::query("ALTER TABLE posts ADD COLUMN IF NOT EXISTS min_tier_id UUID REFERENCES membership_tiers(id) ON DELETE SET NULL")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE posts ADD COLUMN IF NOT EXISTS product_id UUID REFERENCES products(id) ON DELETE SET NULL")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE posts ADD COLUMN IF NOT EXISTS preview TEXT")
+            .execute(&self.pool)
+            .await?;
+
+        // Drafts and scheduled posts; existing posts count as published
+        sqlx::query("ALTER TABLE posts ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'published'")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE posts ADD COLUMN IF NOT EXISTS publish_at TIMESTAMP WITH TIME ZONE")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("ALTER TABLE posts ADD COLUMN IF NOT EXISTS published_at TIMESTAMP WITH TIME ZONE")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("UPDATE posts SET published_at = created_at WHERE status = 'published' AND published_at IS NULL")
+            .execute(&self.pool)
+            .await?;
+
+        // User ids are text everywhere; older databases created posts.user_id as UUID
+        sqlx::query(
+            r#"
+            DO $$
+            BEGIN
+                IF EXISTS (
+                    SELECT 1 FROM information_schema.columns
+                    WHERE table_name = 'posts' AND column_name = 'user_id' AND data_type = 'uuid'
+                ) THEN
+                    ALTER TABLE posts ALTER COLUMN user_id TYPE VARCHAR(255) USING user_id::text;
+                END IF;
+            END
+            $$
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Global tags (no creator) double as site-wide categories
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS tags (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                name VARCHAR(50) NOT NULL,
+                slug VARCHAR(80) NOT NULL,
+                creator_id VARCHAR(255) REFERENCES users(id) ON DELETE CASCADE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS content_tags (
+                tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+                content_type VARCHAR(20) NOT NULL,
+                content_id VARCHAR(255) NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                PRIMARY KEY (tag_id, content_type, content_id)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Ordered media and files attached to a post
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS post_attachments (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+                position INTEGER NOT NULL,
+                url TEXT NOT NULL,
+                mime_type VARCHAR(100) NOT NULL,
+                width INTEGER CHECK (width > 0),
+                height INTEGER CHECK (height > 0),
+                duration_seconds DOUBLE PRECISION CHECK (duration_seconds >= 0),
+                alt_text TEXT,
+                -- NULL follows the post's gating, TRUE/FALSE override it
+                is_premium BOOLEAN,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Attachments used to default to FALSE, which made them public on
+        // gated posts. Once, move those unmarked rows over to inheriting.
+        sqlx::query(
+            r#"
+            DO $$
+            BEGIN
+                IF EXISTS (
+                    SELECT 1 FROM information_schema.columns
+                    WHERE table_name = 'post_attachments' AND column_name = 'is_premium' AND is_nullable = 'NO'
+                ) THEN
+                    ALTER TABLE post_attachments ALTER COLUMN is_premium DROP NOT NULL;
+                    ALTER TABLE post_attachments ALTER COLUMN is_premium DROP DEFAULT;
+                    UPDATE post_attachments SET is_premium = NULL WHERE NOT is_premium;
+                END IF;
+            END $$
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Snapshots of a post after each edit
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS post_revisions (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+                revision INTEGER NOT NULL,
+                title VARCHAR(255) NOT NULL,
+                content TEXT,
+                media_url TEXT,
+                media_type VARCHAR(50),
+                is_premium BOOLEAN NOT NULL DEFAULT FALSE,
+                editor_id VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                UNIQUE (post_id, revision)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Full-text search. Premium post bodies are left out of the index so
+        // searching cannot reveal what is behind the paywall.
+        sqlx::query("CREATE EXTENSION IF NOT EXISTS pg_trgm")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            ALTER TABLE posts ADD COLUMN IF NOT EXISTS search_vector tsvector
+            GENERATED ALWAYS AS (
+                setweight(to_tsvector('english', COALESCE(title, '')), 'A') ||
+                setweight(to_tsvector('english', COALESCE(preview, '')), 'B') ||
+                setweight(to_tsvector('english', CASE WHEN is_premium THEN '' ELSE COALESCE(content, '') END), 'C')
+            ) STORED
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            ALTER TABLE products ADD COLUMN IF NOT EXISTS search_vector tsvector
+            GENERATED ALWAYS AS (
+                setweight(to_tsvector('english', COALESCE(name, '')), 'A') ||
+                setweight(to_tsvector('english', COALESCE(description, '')), 'B')
+            ) STORED
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            ALTER TABLE users ADD COLUMN IF NOT EXISTS search_vector tsvector
+            GENERATED ALWAYS AS (
+                setweight(to_tsvector('simple', COALESCE(display_name, '')), 'A') ||
+                setweight(to_tsvector('simple', COALESCE(username, '')), 'A') ||
+                setweight(to_tsvector('english', COALESCE(bio, '')), 'B')
+            ) STORED
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Articles are created outside these migrations, so only index them when present
+        sqlx::query(
+            r#"
+            DO $$
+            BEGIN
+                IF to_regclass('articles') IS NOT NULL THEN
+                    ALTER TABLE articles ADD COLUMN IF NOT EXISTS search_vector tsvector
+                    GENERATED ALWAYS AS (
+                        setweight(to_tsvector('english', COALESCE(title, '')), 'A') ||
+                        setweight(to_tsvector('english', COALESCE(content, '')), 'B')
+                    ) STORED;
+                    CREATE INDEX IF NOT EXISTS idx_articles_search_vector ON articles USING GIN(search_vector);
+                END IF;
+            END
+            $$
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Polls on posts. A ballot row per voter enforces one vote per user;
+        // a multiple-choice ballot can pick several options.
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS polls (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                post_id UUID NOT NULL UNIQUE REFERENCES posts(id) ON DELETE CASCADE,
+                question VARCHAR(300) NOT NULL,
+                multiple_choice BOOLEAN NOT NULL DEFAULT FALSE,
+                closes_at TIMESTAMP WITH TIME ZONE,
+                subscribers_only BOOLEAN NOT NULL DEFAULT FALSE,
+                hide_results_until_close BOOLEAN NOT NULL DEFAULT FALSE,
+                total_votes INTEGER NOT NULL DEFAULT 0,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS poll_options (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                poll_id UUID NOT NULL REFERENCES polls(id) ON DELETE CASCADE,
+                position INTEGER NOT NULL,
+                label VARCHAR(200) NOT NULL,
+                vote_count INTEGER NOT NULL DEFAULT 0
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS poll_ballots (
+                poll_id UUID NOT NULL REFERENCES polls(id) ON DELETE CASCADE,
+                user_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                PRIMARY KEY (poll_id, user_id)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS poll_votes (
+                poll_id UUID NOT NULL,
+                user_id VARCHAR(255) NOT NULL,
+                option_id UUID NOT NULL REFERENCES poll_options(id) ON DELETE CASCADE,
+                PRIMARY KEY (poll_id, user_id, option_id),
+                FOREIGN KEY (poll_id, user_id) REFERENCES poll_ballots(poll_id, user_id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Creator goals; progress is computed from live subscription and sales data
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS creator_goals (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                creator_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                kind VARCHAR(30) NOT NULL,
+                title VARCHAR(255) NOT NULL,
+                description TEXT,
+                target_amount DOUBLE PRECISION NOT NULL CHECK (target_amount > 0),
+                currency VARCHAR(3) NOT NULL DEFAULT 'USD',
+                reward_description TEXT,
+                deadline TIMESTAMP WITH TIME ZONE,
+                is_public BOOLEAN NOT NULL DEFAULT TRUE,
+                reached_at TIMESTAMP WITH TIME ZONE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+                updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS follows (
+                follower_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                followee_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+                PRIMARY KEY (follower_id, followee_id),
+                CHECK (follower_id <> followee_id)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS user_blocks (
+                blocker_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                blocked_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+                PRIMARY KEY (blocker_id, blocked_id),
+                CHECK (blocker_id <> blocked_id)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS user_mutes (
+                muter_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                muted_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+                PRIMARY KEY (muter_id, muted_id),
+                CHECK (muter_id <> muted_id)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Moderation queue. `target_owner_id` is who posted the reported content.
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS reports (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                reporter_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                target_type VARCHAR(20) NOT NULL,
+                target_id TEXT NOT NULL,
+                target_owner_id VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL,
+                reason VARCHAR(30) NOT NULL,
+                details TEXT,
+                status VARCHAR(20) NOT NULL DEFAULT 'OPEN',
+                resolved_by VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL,
+                resolution_note TEXT,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+                resolved_at TIMESTAMP WITH TIME ZONE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Previous campaign slugs, so old links keep resolving after a rename
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS slug_history (
+                slug VARCHAR(255) PRIMARY KEY,
+                campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+                created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Create indexes
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_posts_user_id ON posts(user_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_products_user_id ON products(user_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_subscriptions_user_id ON subscriptions(user_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_subscriptions_creator_id ON subscriptions(creator_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_campaigns_creator_id ON campaigns(creator_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_purchases_product_status ON purchases(product_id, status)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_purchases_created_at ON purchases(created_at)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_product_reviews_product_id ON product_reviews(product_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_purchases_payment_intent ON purchases(stripe_payment_intent_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_campaigns_status_end_date ON campaigns(status, end_date)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_notifications_user_id ON notifications(user_id, created_at DESC)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_reward_tiers_campaign_id ON campaign_reward_tiers(campaign_id, position)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_reward_fulfillments_tier_id ON reward_fulfillments(reward_tier_id, status)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_stretch_goals_campaign_id ON campaign_stretch_goals(campaign_id, target_amount)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_stream_events_creator_id ON stream_events(creator_id, created_at DESC)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_campaign_updates_campaign_id ON campaign_updates(campaign_id, created_at DESC)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_comments_campaign_id ON comments(campaign_id, created_at)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_comments_parent_id ON comments(parent_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_slug_history_campaign_id ON slug_history(campaign_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_campaigns_search_vector ON campaigns USING GIN(search_vector)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_campaigns_status ON campaigns(status, created_at DESC)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_comments_post_id ON comments(post_id, created_at)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_bookmarks_user_id ON bookmarks(user_id, created_at DESC)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_membership_tiers_creator_id ON membership_tiers(creator_id, price)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_posts_status_publish_at ON posts(status, publish_at)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_post_attachments_post_id ON post_attachments(post_id, position)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_tags_global_slug ON tags(slug) WHERE creator_id IS NULL")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_tags_creator_slug ON tags(creator_id, slug) WHERE creator_id IS NOT NULL")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_content_tags_content ON content_tags(content_type, content_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_posts_search_vector ON posts USING GIN(search_vector)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_products_search_vector ON products USING GIN(search_vector)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_users_search_vector ON users USING GIN(search_vector)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_users_username_trgm ON users USING GIN(username gin_trgm_ops)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_poll_options_poll_id ON poll_options(poll_id, position)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_creator_goals_creator_id ON creator_goals(creator_id, created_at)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_follows_followee_id ON follows(followee_id, created_at DESC)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_user_blocks_blocked_id ON user_blocks(blocked_id)")
+            .execute(&self.pool)
+            .await?;
+
+        // One open report per reporter and target
+        sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_reports_open_per_reporter
+             ON reports(reporter_id, target_type, target_id) WHERE status = 'OPEN'"
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_reports_status ON reports(status, created_at)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_donations_campaign_id ON donations(campaign_id, status)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_donations_authorized ON donations(campaign_id) WHERE status = 'AUTHORIZED'")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_donations_user_id ON donations(user_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_donations_payment_intent ON donations(stripe_payment_intent_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_refunds_target ON refunds(target_type, target_id)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_payment_audit_log_target ON payment_audit_log(target_type, target_id)")
+            .execute(&self.pool)
+            .await?;
+
+        println!("✅ Database migrations completed successfully!");
+        Ok(())
+    }
+}
+
+impl Clone for Database {
+    fn clone(&self) -> Self {
+        Database {
+            pool: self.pool.clone(),
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+impl Database {
+    pub async fn is_admin(&self, user_id: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND role = $2)"
+        )
+        .bind(user_id)
+        .bind(crate::models::user_role::ADMIN)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Admins and moderators
+    pub async fn is_moderator(&self, user_id: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND role = ANY($2))"
+        )
+        .bind(user_id)
+        .bind(crate::models::user_role::STAFF)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Whether `table` exists. Articles, podcasts and events are created
+    /// outside these migrations, so queries that span them check first.
+    pub async fn table_exists(&self, table: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar::<_, bool>("SELECT to_regclass($1) IS NOT NULL")
+            .bind(table)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    pub async fn is_creator(&self, user_id: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND is_creator = TRUE)"
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+    }
 
-impl Database {
     pub async fn get_campaigns(&self, limit: i64, offset: i64) -> Result<Vec<crate::routes::campaigns::Campaign>, sqlx::Error> {
         sqlx::query_as::<_, crate::routes::campaigns::Campaign>(
             "SELECT id, title, description, \"goalAmount\", \"currentAmount\", status, \"createdAt\", \"updatedAt\", \"creatorId\" FROM \"Campaign\" ORDER BY \"createdAt\" DESC LIMIT $1 OFFSET $2"
@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use crate::{
+    database::Database,
+    routes::{
+        campaigns::close_expired_campaigns,
+        donations::{expire_stale_donations, settle_closed_pledges},
+        goals::mark_reached_goals,
+        posts::publish_due_posts,
+    },
+};
+
+const CAMPAIGN_CLOSE_INTERVAL: Duration = Duration::from_secs(60);
+const DONATION_EXPIRY_INTERVAL: Duration = Duration::from_secs(300);
+const POST_PUBLISH_INTERVAL: Duration = Duration::from_secs(60);
+const GOAL_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Start the periodic background jobs. Each job runs in its own task on its
+/// own interval, so a slow job does not hold up the others, and logs failures
+/// instead of stopping, so one bad tick does not kill the loop.
+pub fn spawn(db: Database) {
+    let db_for_campaigns = db.clone();
+    tokio::spawn(async move {
+        let db = db_for_campaigns;
+        let mut interval = tokio::time::interval(CAMPAIGN_CLOSE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match close_expired_campaigns(&db).await {
+                Ok(0) => {}
+                Ok(closed) => tracing::info!("Closed {} expired campaign(s)", closed),
+                Err(e) => tracing::error!("Failed to close expired campaigns: {}", e),
+            }
+
+            // All-or-nothing pledges on campaigns that just closed
+            match settle_closed_pledges(&db).await {
+                Ok(0) => {}
+                Ok(settled) => tracing::info!("Settled {} all-or-nothing pledge(s)", settled),
+                Err(e) => tracing::error!("Failed to settle pledges: {}", e),
+            }
+        }
+    });
+
+    let db_for_donations = db.clone();
+    tokio::spawn(async move {
+        let db = db_for_donations;
+        let mut interval = tokio::time::interval(DONATION_EXPIRY_INTERVAL);
+        loop {
+            interval.tick().await;
+            match expire_stale_donations(&db).await {
+                Ok(0) => {}
+                Ok(expired) => tracing::info!("Expired {} unpaid donation(s)", expired),
+                Err(e) => tracing::error!("Failed to expire unpaid donations: {}", e),
+            }
+        }
+    });
+
+    let db_for_posts = db.clone();
+    tokio::spawn(async move {
+        let db = db_for_posts;
+        let mut interval = tokio::time::interval(POST_PUBLISH_INTERVAL);
+        loop {
+            interval.tick().await;
+            match publish_due_posts(&db).await {
+                Ok(0) => {}
+                Ok(published) => tracing::info!("Published {} scheduled post(s)", published),
+                Err(e) => tracing::error!("Failed to publish scheduled posts: {}", e),
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(GOAL_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            match mark_reached_goals(&db).await {
+                Ok(0) => {}
+                Ok(reached) => tracing::info!("Marked {} creator goal(s) as reached", reached),
+                Err(e) => tracing::error!("Failed to check creator goals: {}", e),
+            }
+        }
+    });
+}
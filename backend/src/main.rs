@@ -14,15 +14,20 @@ use tower_http::{
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod auth;
+mod cache;
 mod config;
 mod database;
+mod jobs;
 mod middleware;
 mod models;
+mod notifications;
+mod payments;
 mod routes;
+mod validation;
 
 use config::Config;
 use database::Database;
-use routes::{auth::auth_routes, users::user_routes, posts::post_routes, products::product_routes, campaigns::campaign_routes, events::event_routes, creators::creator_routes, articles::articles_routes, podcasts::podcast_routes};
+use routes::{auth::auth_routes, users::user_routes, posts::post_routes, products::product_routes, campaigns::campaign_routes, comments::comment_routes, donations::donation_routes, events::event_routes, feed::feed_routes, goals::goal_routes, notifications::notification_routes, creators::creator_routes, articles::articles_routes, podcasts::podcast_routes, refunds::refund_routes, reports::report_routes, search::search_routes, stream::stream_routes, tags::tag_routes, tiers::tier_routes, webhooks::webhook_routes};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -44,6 +49,9 @@ async fn main() -> anyhow::Result<()> {
     // Run migrations
     db.run_migrations().await?;
 
+    // Start background jobs (campaign auto-close, ...)
+    jobs::spawn(db.clone());
+
     // Build our application with routes
     let app = Router::new()
         .route("/health", get(health_check))
@@ -53,10 +61,21 @@ async fn main() -> anyhow::Result<()> {
         .nest("/api/posts", post_routes())
         .nest("/api/products", product_routes())
         .nest("/api/campaigns", campaign_routes())
+        .nest("/api/donations", donation_routes())
+        .nest("/api/comments", comment_routes())
         .nest("/api/events", event_routes())
+        .nest("/api/feed", feed_routes())
         .nest("/api/articles", articles_routes())
         .nest("/api/podcasts", podcast_routes())
-        .route("/api/notifications", get(get_notifications))
+        .nest("/api/refunds", refund_routes())
+        .nest("/api/reports", report_routes())
+        .nest("/api/webhooks", webhook_routes())
+        .nest("/api/stream", stream_routes())
+        .nest("/api/search", search_routes())
+        .nest("/api/tags", tag_routes())
+        .nest("/api/goals", goal_routes())
+        .nest("/api/tiers", tier_routes())
+        .nest("/api/notifications", notification_routes())
         .route("/api/subscriptions/my-subscribers", get(get_my_subscribers))
         .layer(
             ServiceBuilder::new()
@@ -92,16 +111,6 @@ async fn health_check() -> &'static str {
     "OK"
 }
 
-async fn get_notifications() -> Result<Json<serde_json::Value>, StatusCode> {
-    // Mock notifications for now
-    let response = serde_json::json!({
-        "success": true,
-        "data": []
-    });
-    
-    Ok(Json(response))
-}
-
 async fn get_my_subscribers() -> Result<Json<serde_json::Value>, StatusCode> {
     // Mock subscribers for now
     let response = serde_json::json!({
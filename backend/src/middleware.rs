@@ -24,13 +24,23 @@ pub async fn auth_middleware(
        (path.starts_with("/api/campaigns") && request.method() == "GET") ||
        path.starts_with("/api/events") ||
        (path.starts_with("/api/posts") && request.method() == "GET") ||
-       path.starts_with("/api/products") ||
+       (path.starts_with("/api/products") && request.method() == "GET") ||
        path.starts_with("/api/articles") ||
        path.starts_with("/api/podcasts") ||
        path.starts_with("/api/notifications") ||
        path.starts_with("/api/subscriptions") ||
+       path.starts_with("/api/webhooks") ||
+       path.starts_with("/api/stream") ||
+       (path.starts_with("/api/tags") && request.method() == "GET") ||
+       (path.starts_with("/api/search") && request.method() == "GET") ||
+       (path.starts_with("/api/goals") && request.method() == "GET") ||
+       (path.starts_with("/api/users/") && (path.ends_with("/followers") || path.ends_with("/following")) && request.method() == "GET") ||
        (path.starts_with("/api/") && request.method() == "OPTIONS") {
         println!("✅ Skipping auth for: {}", path);
+        // Public routes still get to know who is calling when a valid token is sent
+        if let Some(claims) = optional_claims(&request) {
+            request.extensions_mut().insert(claims);
+        }
         return Ok(next.run(request).await);
     }
 
@@ -77,6 +87,16 @@ pub async fn auth_middleware(
     Ok(next.run(request).await)
 }
 
+fn optional_claims(request: &Request) -> Option<crate::auth::Claims> {
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "))?;
+    let config = Config::from_env().ok()?;
+    verify_jwt(token, &config.jwt_secret).ok()
+}
+
 pub mod auth {
     use axum::{
         extract::{FromRequestParts, State},
@@ -24,8 +24,67 @@ pub struct Post {
     pub media_url: Option<String>,
     pub media_type: Option<String>,
     pub is_premium: bool,
+    /// Lowest membership tier that can read the post
+    pub min_tier_id: Option<Uuid>,
+    /// Buyers of this product can read the post
+    pub product_id: Option<Uuid>,
+    /// Teaser shown instead of `content` while the post is locked
+    pub preview: Option<String>,
+    pub like_count: i32,
+    pub comment_count: i32,
+    pub comments_enabled: bool,
+    /// One of `post_status`; only published posts are listed publicly
+    pub status: String,
+    /// When a scheduled post goes out
+    pub publish_at: Option<DateTime<Utc>>,
+    pub published_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Whether the viewer lacks access; only selected by the post listings
+    #[sqlx(default)]
+    pub locked: bool,
+    #[sqlx(default)]
+    pub required_tier_name: Option<String>,
+    #[sqlx(default)]
+    pub required_tier_price: Option<f64>,
+    /// Whether the viewer is neither the author nor an active subscriber,
+    /// which gates premium attachments on otherwise free posts
+    #[sqlx(default)]
+    #[serde(skip)]
+    pub members_locked: bool,
+}
+
+impl Post {
+    /// Whether reading the post needs a tier, a subscription or a purchase
+    pub fn is_gated(&self) -> bool {
+        self.is_premium || self.min_tier_id.is_some() || self.product_id.is_some()
+    }
+}
+
+/// A post together with its ordered attachments
+#[derive(Debug, Serialize)]
+pub struct PostWithAttachments {
+    #[serde(flatten)]
+    pub post: Post,
+    pub attachments: Vec<PostAttachment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PostAttachment {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub position: i32,
+    /// Withheld when the viewer lacks access to the attachment
+    pub url: Option<String>,
+    pub mime_type: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub duration_seconds: Option<f64>,
+    pub alt_text: Option<String>,
+    /// `None` follows the post's gating; `Some(false)` is free for everyone
+    /// and `Some(true)` needs access even on a free post
+    pub is_premium: Option<bool>,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -39,6 +98,9 @@ pub struct Product {
     pub image_url: Option<String>,
     pub is_digital: bool,
     pub download_url: Option<String>,
+    pub featured: bool,
+    pub rating_average: Option<f64>,
+    pub rating_count: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -68,6 +130,187 @@ pub struct Purchase {
     pub created_at: DateTime<Utc>,
 }
 
+// Values stored in `users.role`
+pub mod user_role {
+    pub const ADMIN: &str = "admin";
+    pub const MODERATOR: &str = "moderator";
+
+    /// Roles that work the report queue
+    pub const STAFF: &[&str] = &[ADMIN, MODERATOR];
+}
+
+// Purchase lifecycle states stored in `purchases.status`
+pub mod purchase_status {
+    /// Checkout started; completed by the `payment_intent.succeeded` webhook
+    pub const PENDING: &str = "PENDING";
+    pub const COMPLETED: &str = "COMPLETED";
+    pub const PARTIALLY_REFUNDED: &str = "PARTIALLY_REFUNDED";
+    pub const REFUNDED: &str = "REFUNDED";
+    pub const DISPUTED: &str = "DISPUTED";
+
+    /// Purchases that still count as a sale and grant access
+    pub const SETTLED: &[&str] = &[COMPLETED, PARTIALLY_REFUNDED];
+
+    pub fn can_transition(from: &str, to: &str) -> bool {
+        matches!(
+            (from, to),
+            (PENDING, COMPLETED)
+                | (COMPLETED, PARTIALLY_REFUNDED)
+                | (COMPLETED, REFUNDED)
+                | (COMPLETED, DISPUTED)
+                | (PARTIALLY_REFUNDED, PARTIALLY_REFUNDED)
+                | (PARTIALLY_REFUNDED, REFUNDED)
+                | (PARTIALLY_REFUNDED, DISPUTED)
+                | (DISPUTED, COMPLETED)
+                | (DISPUTED, PARTIALLY_REFUNDED)
+                | (DISPUTED, REFUNDED)
+        )
+    }
+}
+
+// Donation states stored in `donations.status`. Once paid, donations follow
+// the same refund and dispute states as purchases.
+pub mod donation_status {
+    pub const PENDING: &str = "PENDING";
+    /// All-or-nothing pledge: card authorized, captured only if the goal is met
+    pub const AUTHORIZED: &str = "AUTHORIZED";
+    /// All-or-nothing pledge whose authorization was cancelled
+    pub const RELEASED: &str = "RELEASED";
+    pub const FAILED: &str = "FAILED";
+    pub use super::purchase_status::{COMPLETED, PARTIALLY_REFUNDED, SETTLED};
+
+    /// Donations that count as backing a campaign, including uncaptured pledges
+    pub const BACKED: &[&str] = &[AUTHORIZED, COMPLETED, PARTIALLY_REFUNDED];
+}
+
+// Values of `campaigns.funding_model`
+pub mod funding_model {
+    /// Every donation is charged immediately
+    pub const KEEP_IT_ALL: &str = "keep_it_all";
+    /// Pledges are authorized and only captured if the goal is met by `end_date`
+    pub const ALL_OR_NOTHING: &str = "all_or_nothing";
+
+    pub fn is_valid(value: &str) -> bool {
+        matches!(value, KEEP_IT_ALL | ALL_OR_NOTHING)
+    }
+}
+
+// Campaign lifecycle stored in `campaigns.status`
+pub mod campaign_status {
+    pub const DRAFT: &str = "DRAFT";
+    pub const ACTIVE: &str = "ACTIVE";
+    pub const FUNDED: &str = "FUNDED";
+    pub const ENDED: &str = "ENDED";
+    pub const CANCELLED: &str = "CANCELLED";
+
+    pub fn can_transition(from: &str, to: &str) -> bool {
+        matches!(
+            (from, to),
+            (DRAFT, ACTIVE)
+                | (DRAFT, CANCELLED)
+                | (ACTIVE, FUNDED)
+                | (ACTIVE, ENDED)
+                | (ACTIVE, CANCELLED)
+        )
+    }
+}
+
+// Values of `campaigns.category`, matching the frontend's `CampaignCategory`
+pub mod campaign_category {
+    pub const OTHER: &str = "OTHER";
+    pub const ALL: &[&str] = &[
+        "TECHNOLOGY", "CREATIVE", "COMMUNITY", "BUSINESS", "EDUCATION", "HEALTH", "ENVIRONMENT", OTHER,
+    ];
+
+    pub fn is_valid(value: &str) -> bool {
+        ALL.contains(&value)
+    }
+}
+
+// Reward fulfillment states stored in `reward_fulfillments.status`
+pub mod fulfillment_status {
+    pub const PENDING: &str = "PENDING";
+    pub const SHIPPED: &str = "SHIPPED";
+    pub const DELIVERED: &str = "DELIVERED";
+    pub const CANCELLED: &str = "CANCELLED";
+
+    pub fn can_transition(from: &str, to: &str) -> bool {
+        matches!(
+            (from, to),
+            (PENDING, SHIPPED) | (PENDING, DELIVERED) | (SHIPPED, DELIVERED) | (PENDING, CANCELLED)
+        )
+    }
+}
+
+// Kinds of content users can bookmark, stored in `bookmarks.content_type`
+pub mod content_type {
+    pub const POST: &str = "POST";
+    pub const ARTICLE: &str = "ARTICLE";
+    pub const EVENT: &str = "EVENT";
+    pub const PODCAST: &str = "PODCAST";
+    pub const CAMPAIGN_UPDATE: &str = "CAMPAIGN_UPDATE";
+    pub const PRODUCT: &str = "PRODUCT";
+}
+
+// Subscription states we write ourselves; the rest mirror Stripe
+pub mod subscription_status {
+    pub const ACTIVE: &str = "active";
+    pub const TRIALING: &str = "trialing";
+    pub const CANCELED: &str = "canceled";
+
+    /// Subscriptions that unlock a creator's premium posts
+    pub const ENTITLED: &[&str] = &[ACTIVE, TRIALING];
+}
+
+pub mod post_status {
+    pub const DRAFT: &str = "draft";
+    pub const SCHEDULED: &str = "scheduled";
+    pub const PUBLISHED: &str = "published";
+}
+
+// What can be reported, stored in `reports.target_type`
+pub mod report_target {
+    pub use super::content_type::{POST, PRODUCT};
+    pub const COMMENT: &str = "COMMENT";
+    pub const CAMPAIGN: &str = "CAMPAIGN";
+    pub const USER: &str = "USER";
+}
+
+// Values of `reports.reason`
+pub mod report_reason {
+    pub const ALL: &[&str] = &[
+        "SPAM", "HARASSMENT", "HATE_SPEECH", "VIOLENCE", "SEXUAL_CONTENT", "SCAM", "INTELLECTUAL_PROPERTY", "OTHER",
+    ];
+
+    pub fn is_valid(value: &str) -> bool {
+        ALL.contains(&value)
+    }
+}
+
+// Moderation states stored in `reports.status`
+pub mod report_status {
+    pub const OPEN: &str = "OPEN";
+    /// A moderator acted on the report
+    pub const RESOLVED: &str = "RESOLVED";
+    /// A moderator found nothing to act on
+    pub const DISMISSED: &str = "DISMISSED";
+}
+
+// Values of `creator_goals.kind`, matching the frontend's goal types
+pub mod goal_kind {
+    /// Subscription charges plus product sales made in the current calendar month
+    pub const REVENUE: &str = "REVENUE";
+    /// Subscribers with an entitled subscription right now
+    pub const SUBSCRIBERS: &str = "SUBSCRIBERS";
+    /// Product sales since the goal was created
+    pub const ONE_OFF: &str = "ONE_OFF";
+    pub const ALL: &[&str] = &[REVENUE, SUBSCRIBERS, ONE_OFF];
+
+    pub fn is_valid(value: &str) -> bool {
+        ALL.contains(&value)
+    }
+}
+
 // Request/Response DTOs
 #[derive(Debug, Deserialize)]
 pub struct CreateUserRequest {
@@ -85,6 +328,30 @@ pub struct CreatePostRequest {
     pub media_url: Option<String>,
     pub media_type: Option<String>,
     pub is_premium: Option<bool>,
+    pub min_tier_id: Option<Uuid>,
+    pub product_id: Option<Uuid>,
+    pub preview: Option<String>,
+    pub comments_enabled: Option<bool>,
+    /// `draft`, `scheduled` or `published` (the default, or `scheduled` when `publish_at` is set)
+    pub status: Option<String>,
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Replaces all attachments, in order, when present
+    pub attachments: Option<Vec<AttachmentRequest>>,
+    /// Tag names; replaces the post's tags when present
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AttachmentRequest {
+    pub url: String,
+    pub mime_type: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub duration_seconds: Option<f64>,
+    pub alt_text: Option<String>,
+    /// Omit to follow the post's gating; `false` makes the attachment free,
+    /// `true` gates it even on a free post
+    pub is_premium: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -96,6 +363,9 @@ pub struct CreateProductRequest {
     pub image_url: Option<String>,
     pub is_digital: Option<bool>,
     pub download_url: Option<String>,
+    pub featured: Option<bool>,
+    /// Tag names; replaces the product's tags when present
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -113,3 +383,33 @@ pub struct GitHubUser {
     pub avatar_url: String,
     pub bio: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn purchases_complete_only_from_pending() {
+        use purchase_status::*;
+
+        assert!(can_transition(PENDING, COMPLETED));
+        assert!(!can_transition(PENDING, REFUNDED));
+        assert!(!can_transition(REFUNDED, COMPLETED));
+    }
+
+    #[test]
+    fn refunds_and_disputes_follow_the_allowed_paths() {
+        use purchase_status::*;
+
+        assert!(can_transition(COMPLETED, PARTIALLY_REFUNDED));
+        assert!(can_transition(PARTIALLY_REFUNDED, PARTIALLY_REFUNDED));
+        assert!(can_transition(PARTIALLY_REFUNDED, REFUNDED));
+        assert!(can_transition(COMPLETED, DISPUTED));
+        assert!(can_transition(DISPUTED, COMPLETED));
+        assert!(can_transition(DISPUTED, REFUNDED));
+        assert!(!can_transition(COMPLETED, COMPLETED));
+        assert!(!can_transition(REFUNDED, PARTIALLY_REFUNDED));
+        assert!(!can_transition(REFUNDED, DISPUTED));
+        assert!(!can_transition(COMPLETED, "UNKNOWN"));
+    }
+}
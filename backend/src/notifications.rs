@@ -0,0 +1,108 @@
+use uuid::Uuid;
+
+use crate::{
+    models::{donation_status, subscription_status},
+    routes::blocks::hidden_users,
+};
+
+// Values stored in `notifications.kind`
+pub mod kind {
+    pub const CAMPAIGN_FUNDED: &str = "campaign_funded";
+    pub const CAMPAIGN_ENDED: &str = "campaign_ended";
+    pub const CAMPAIGN_CANCELLED: &str = "campaign_cancelled";
+    pub const STRETCH_GOAL_UNLOCKED: &str = "stretch_goal_unlocked";
+    pub const CAMPAIGN_UPDATE: &str = "campaign_update";
+    pub const COMMENT_REPLY: &str = "comment_reply";
+    pub const POST_PUBLISHED: &str = "post_published";
+    pub const GOAL_REACHED: &str = "goal_reached";
+    pub const NEW_FOLLOWER: &str = "new_follower";
+}
+
+/// A notification to fan out to one or more users. Recipients who blocked,
+/// were blocked by or muted the actor are skipped.
+pub struct NewNotification<'a> {
+    pub kind: &'a str,
+    pub title: String,
+    pub message: Option<String>,
+    pub link: Option<String>,
+    pub actor_id: Option<&'a str>,
+}
+
+pub async fn notify_user(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: &str,
+    notification: &NewNotification<'_>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(&format!(
+        "INSERT INTO notifications (user_id, kind, title, message, link, actor_id)
+         SELECT $1, $2, $3, $4, $5, $6
+         WHERE $6::text IS NULL OR $6 NOT IN {}",
+        hidden_users("$1")
+    ))
+    .bind(user_id)
+    .bind(notification.kind)
+    .bind(&notification.title)
+    .bind(&notification.message)
+    .bind(&notification.link)
+    .bind(notification.actor_id)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Notify everyone backing the campaign, including uncaptured all-or-nothing
+/// pledges. Returns how many backers were notified.
+pub async fn notify_campaign_backers(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    campaign_id: Uuid,
+    notification: &NewNotification<'_>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(&format!(
+        "INSERT INTO notifications (user_id, kind, title, message, link, actor_id)
+         SELECT DISTINCT d.user_id, $3, $4, $5, $6, $7
+         FROM donations d
+         WHERE d.campaign_id = $1 AND d.status = ANY($2) AND d.user_id IS NOT NULL
+           AND ($7::text IS NULL OR $7 NOT IN {})",
+        hidden_users("d.user_id")
+    ))
+    .bind(campaign_id)
+    .bind(donation_status::BACKED)
+    .bind(notification.kind)
+    .bind(&notification.title)
+    .bind(&notification.message)
+    .bind(&notification.link)
+    .bind(notification.actor_id)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Notify the creator's current subscribers. Returns how many were notified.
+pub async fn notify_creator_subscribers(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    creator_id: &str,
+    notification: &NewNotification<'_>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(&format!(
+        "INSERT INTO notifications (user_id, kind, title, message, link, actor_id)
+         SELECT DISTINCT s.user_id, $3, $4, $5, $6, $7
+         FROM subscriptions s
+         WHERE s.creator_id = $1 AND s.status = ANY($2)
+           AND (s.current_period_end IS NULL OR s.current_period_end > NOW())
+           AND ($7::text IS NULL OR $7 NOT IN {})",
+        hidden_users("s.user_id")
+    ))
+    .bind(creator_id)
+    .bind(subscription_status::ENTITLED)
+    .bind(notification.kind)
+    .bind(&notification.title)
+    .bind(&notification.message)
+    .bind(&notification.link)
+    .bind(notification.actor_id)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(result.rows_affected())
+}
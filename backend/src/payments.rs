@@ -0,0 +1,292 @@
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::config::Config;
+
+const STRIPE_API_BASE: &str = "https://api.stripe.com/v1";
+// Reject webhook deliveries whose signature timestamp is older than this
+const WEBHOOK_TOLERANCE_SECS: u64 = 300;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PaymentError {
+    #[error("Payment gateway is not configured")]
+    NotConfigured,
+    #[error("Payment gateway request failed: {0}")]
+    Request(String),
+    #[error("Payment gateway rejected the request: {0}")]
+    Rejected(String),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StripeRefund {
+    pub id: String,
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StripePaymentIntent {
+    pub id: String,
+    pub client_secret: Option<String>,
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StripeSubscription {
+    pub id: String,
+    pub status: String,
+}
+
+/// Thin wrapper around the Stripe REST API. We talk to Stripe with plain
+/// form-encoded requests rather than pulling in an SDK.
+pub struct StripeClient {
+    secret_key: String,
+}
+
+impl StripeClient {
+    pub fn from_config(config: &Config) -> Result<Self, PaymentError> {
+        if config.stripe_secret_key.is_empty() {
+            return Err(PaymentError::NotConfigured);
+        }
+
+        Ok(StripeClient {
+            secret_key: config.stripe_secret_key.clone(),
+        })
+    }
+
+    /// Create a payment intent the client confirms with Stripe.js. With
+    /// `manual_capture` the card is only authorized until `capture_payment_intent`.
+    pub async fn create_payment_intent(
+        &self,
+        amount_cents: i64,
+        currency: &str,
+        manual_capture: bool,
+        metadata: &[(&str, String)],
+    ) -> Result<StripePaymentIntent, PaymentError> {
+        let mut form = vec![
+            ("amount".to_string(), amount_cents.to_string()),
+            ("currency".to_string(), currency.to_lowercase()),
+            ("automatic_payment_methods[enabled]".to_string(), "true".to_string()),
+        ];
+        if manual_capture {
+            form.push(("capture_method".to_string(), "manual".to_string()));
+        }
+        for (key, value) in metadata {
+            form.push((format!("metadata[{}]", key), value.clone()));
+        }
+
+        self.post_form("payment_intents", &form).await
+    }
+
+    /// Capture a previously authorized payment intent in full.
+    pub async fn capture_payment_intent(&self, payment_intent_id: &str) -> Result<StripePaymentIntent, PaymentError> {
+        self.post_form(&format!("payment_intents/{}/capture", payment_intent_id), &[]).await
+    }
+
+    /// Cancel an uncaptured payment intent, releasing the authorization.
+    pub async fn cancel_payment_intent(&self, payment_intent_id: &str) -> Result<StripePaymentIntent, PaymentError> {
+        self.post_form(&format!("payment_intents/{}/cancel", payment_intent_id), &[]).await
+    }
+
+    /// Refund a payment intent. `amount_cents` of `None` refunds the remaining balance.
+    pub async fn create_refund(
+        &self,
+        payment_intent_id: &str,
+        amount_cents: Option<i64>,
+        reason: Option<&str>,
+    ) -> Result<StripeRefund, PaymentError> {
+        let mut form = vec![("payment_intent".to_string(), payment_intent_id.to_string())];
+        if let Some(amount) = amount_cents {
+            form.push(("amount".to_string(), amount.to_string()));
+        }
+        if let Some(reason) = reason {
+            form.push(("metadata[reason]".to_string(), reason.to_string()));
+        }
+
+        let refund: StripeRefund = self.post_form("refunds", &form).await?;
+
+        if refund.status == "failed" || refund.status == "canceled" {
+            return Err(PaymentError::Rejected(format!("refund {} {}", refund.id, refund.status)));
+        }
+
+        Ok(refund)
+    }
+
+    /// Cancel a subscription immediately so Stripe stops billing it.
+    pub async fn cancel_subscription(&self, subscription_id: &str) -> Result<StripeSubscription, PaymentError> {
+        let subscription: StripeSubscription = self
+            .send_form(reqwest::Method::DELETE, &format!("subscriptions/{}", subscription_id), &[])
+            .await?;
+
+        if subscription.status != "canceled" {
+            return Err(PaymentError::Rejected(format!("subscription {} {}", subscription.id, subscription.status)));
+        }
+
+        Ok(subscription)
+    }
+
+    async fn post_form<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        form: &[(String, String)],
+    ) -> Result<T, PaymentError> {
+        self.send_form(reqwest::Method::POST, path, form).await
+    }
+
+    async fn send_form<T: serde::de::DeserializeOwned>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        form: &[(String, String)],
+    ) -> Result<T, PaymentError> {
+        let response = reqwest::Client::new()
+            .request(method, &format!("{}/{}", STRIPE_API_BASE, path))
+            .basic_auth(&self.secret_key, None::<&str>)
+            .form(form)
+            .send()
+            .await
+            .map_err(|e| PaymentError::Request(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(PaymentError::Rejected(body));
+        }
+
+        response
+            .json::<T>()
+            .await
+            .map_err(|e| PaymentError::Request(e.to_string()))
+    }
+}
+
+/// Currencies Stripe charges in whole units, without a minor unit
+const ZERO_DECIMAL_CURRENCIES: &[&str] = &[
+    "BIF", "CLP", "DJF", "GNF", "JPY", "KMF", "KRW", "MGA", "PYG", "RWF", "UGX", "VND", "VUV", "XAF", "XOF", "XPF",
+];
+
+fn minor_units_per_major(currency: &str) -> f64 {
+    if ZERO_DECIMAL_CURRENCIES.iter().any(|c| c.eq_ignore_ascii_case(currency)) {
+        1.0
+    } else {
+        100.0
+    }
+}
+
+/// Convert a major-unit amount (e.g. dollars) to Stripe's minor units.
+pub fn to_minor_units(amount: f64, currency: &str) -> i64 {
+    (amount * minor_units_per_major(currency)).round() as i64
+}
+
+/// Convert a Stripe minor-unit amount back to major units.
+pub fn from_minor_units(amount: i64, currency: &str) -> f64 {
+    amount as f64 / minor_units_per_major(currency)
+}
+
+/// Verify a `Stripe-Signature` header against the raw request body.
+pub fn verify_webhook_signature(payload: &[u8], signature_header: &str, secret: &str) -> bool {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in signature_header.split(',') {
+        match part.split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+
+    let Some(timestamp) = timestamp else {
+        return false;
+    };
+    if chrono::Utc::now().timestamp().abs_diff(timestamp) > WEBHOOK_TOLERANCE_SECS {
+        return false;
+    }
+
+    signatures.into_iter().any(|signature| {
+        let Ok(expected) = hex::decode(signature) else {
+            return false;
+        };
+        let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+            return false;
+        };
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(payload);
+        mac.verify_slice(&expected).is_ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "whsec_test";
+    const PAYLOAD: &[u8] = br#"{"id":"evt_1","type":"payment_intent.succeeded"}"#;
+
+    fn signature(payload: &[u8], timestamp: i64, secret: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}.", timestamp).as_bytes());
+        mac.update(payload);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn header(payload: &[u8], timestamp: i64, secret: &str) -> String {
+        format!("t={},v1={}", timestamp, signature(payload, timestamp, secret))
+    }
+
+    #[test]
+    fn converts_amounts_using_the_currency_minor_unit() {
+        assert_eq!(to_minor_units(12.34, "USD"), 1234);
+        assert_eq!(to_minor_units(12.34, "eur"), 1234);
+        assert_eq!(to_minor_units(1500.0, "JPY"), 1500);
+        assert_eq!(from_minor_units(1234, "usd"), 12.34);
+        assert_eq!(from_minor_units(1500, "jpy"), 1500.0);
+    }
+
+    #[test]
+    fn accepts_a_valid_signature() {
+        let now = chrono::Utc::now().timestamp();
+        assert!(verify_webhook_signature(PAYLOAD, &header(PAYLOAD, now, SECRET), SECRET));
+    }
+
+    #[test]
+    fn accepts_any_matching_v1_signature() {
+        let now = chrono::Utc::now().timestamp();
+        let header = format!("t={},v1=deadbeef,v1={}", now, signature(PAYLOAD, now, SECRET));
+        assert!(verify_webhook_signature(PAYLOAD, &header, SECRET));
+    }
+
+    #[test]
+    fn rejects_a_timestamp_outside_the_tolerance() {
+        let stale = chrono::Utc::now().timestamp() - WEBHOOK_TOLERANCE_SECS as i64 - 1;
+        assert!(!verify_webhook_signature(PAYLOAD, &header(PAYLOAD, stale, SECRET), SECRET));
+
+        let future = chrono::Utc::now().timestamp() + WEBHOOK_TOLERANCE_SECS as i64 + 1;
+        assert!(!verify_webhook_signature(PAYLOAD, &header(PAYLOAD, future, SECRET), SECRET));
+    }
+
+    #[test]
+    fn rejects_extreme_timestamps_without_overflowing() {
+        for timestamp in [i64::MIN, i64::MAX] {
+            assert!(!verify_webhook_signature(PAYLOAD, &header(PAYLOAD, timestamp, SECRET), SECRET));
+        }
+    }
+
+    #[test]
+    fn rejects_a_tampered_payload_or_wrong_secret() {
+        let now = chrono::Utc::now().timestamp();
+        let header = header(PAYLOAD, now, SECRET);
+        assert!(!verify_webhook_signature(br#"{"id":"evt_2"}"#, &header, SECRET));
+        assert!(!verify_webhook_signature(PAYLOAD, &header, "whsec_other"));
+    }
+
+    #[test]
+    fn rejects_a_malformed_header() {
+        let now = chrono::Utc::now().timestamp();
+        let valid = signature(PAYLOAD, now, SECRET);
+        assert!(!verify_webhook_signature(PAYLOAD, "", SECRET));
+        assert!(!verify_webhook_signature(PAYLOAD, &format!("v1={}", valid), SECRET));
+        assert!(!verify_webhook_signature(PAYLOAD, &format!("t=soon,v1={}", valid), SECRET));
+        assert!(!verify_webhook_signature(PAYLOAD, &format!("t={}", now), SECRET));
+        assert!(!verify_webhook_signature(PAYLOAD, &format!("t={},v1=not-hex", now), SECRET));
+    }
+}
@@ -2,13 +2,18 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
-    routing::get,
+    routing::{get, put},
     Router,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::database::Database;
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::content_type,
+    routes::tags::{set_content_tags, tag_filter},
+};
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Article {
@@ -28,6 +33,13 @@ pub struct ArticleQuery {
     pub limit: Option<u32>,
     #[serde(rename = "authorId")]
     pub author_id: Option<String>,
+    /// Only articles carrying a tag with this slug
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ArticleTagsRequest {
+    pub tags: Vec<String>,
 }
 
 pub fn article_routes() -> Router<Database> {
@@ -60,33 +72,20 @@ async fn get_articles(
     
     eprintln!("Articles API called with params: {:?}", params);
 
-    let articles = if let Some(author_id) = &params.author_id {
-        eprintln!("Filtering articles by author_id: {}", author_id);
-        sqlx::query_as::<_, Article>(
-            "SELECT * FROM articles WHERE author_id = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3"
-        )
-        .bind(author_id)
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(&db.pool)
-        .await
-        .map_err(|e| {
-            eprintln!("Error fetching articles: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-    } else {
-        sqlx::query_as::<_, Article>(
-            "SELECT * FROM articles ORDER BY created_at DESC LIMIT $1 OFFSET $2"
-        )
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(&db.pool)
-        .await
-        .map_err(|e| {
-            eprintln!("Error fetching articles: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-    };
+    let articles = sqlx::query_as::<_, Article>(&format!(
+        "SELECT * FROM articles a WHERE ($1::text IS NULL OR a.author_id = $1) AND {} ORDER BY a.created_at DESC LIMIT $3 OFFSET $4",
+        tag_filter(content_type::ARTICLE, "a.id", "$2")
+    ))
+    .bind(&params.author_id)
+    .bind(&params.tag)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching articles: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
     let total = articles.len();
     let response = ArticlesResponse {
@@ -120,8 +119,39 @@ async fn get_article_by_slug(
     Ok(Json(article))
 }
 
+async fn set_article_tags(
+    State(db): State<Database>,
+    Path(slug): Path<String>,
+    claims: Claims,
+    Json(payload): Json<ArticleTagsRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let (id, author_id) = sqlx::query_as::<_, (Uuid, String)>(
+        "SELECT id, author_id FROM articles WHERE slug = $1 FOR UPDATE"
+    )
+    .bind(&slug)
+    .fetch_optional(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    if author_id != claims.sub {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    set_content_tags(&mut tx, &claims.sub, content_type::ARTICLE, &id.to_string(), &payload.tags).await?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true
+    })))
+}
+
 pub fn articles_routes() -> Router<Database> {
     Router::new()
         .route("/", get(get_articles))
         .route("/:slug", get(get_article_by_slug))
+        .route("/:slug/tags", put(set_article_tags))
 }
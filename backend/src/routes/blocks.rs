@@ -0,0 +1,247 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{auth::Claims, database::Database};
+
+/// A user you blocked or muted
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SilencedUser {
+    pub id: String,
+    pub username: String,
+    pub name: Option<String>,
+    pub avatar: Option<String>,
+    pub since: DateTime<Utc>,
+}
+
+/// Users whose content is hidden from `viewer`, a bind parameter or column:
+/// anyone blocked either way, and anyone the viewer muted. Empty for a null viewer.
+pub(crate) fn hidden_users(viewer: &str) -> String {
+    format!(
+        "(SELECT blocked_id FROM user_blocks WHERE blocker_id = {v}
+          UNION SELECT blocker_id FROM user_blocks WHERE blocked_id = {v}
+          UNION SELECT muted_id FROM user_mutes WHERE muter_id = {v})",
+        v = viewer
+    )
+}
+
+/// Users blocked by `viewer` or who blocked them. Unlike `hidden_users` this
+/// leaves muted users in, for content the viewer opens directly.
+pub(crate) fn blocked_users(viewer: &str) -> String {
+    format!(
+        "(SELECT blocked_id FROM user_blocks WHERE blocker_id = {v}
+          UNION SELECT blocker_id FROM user_blocks WHERE blocked_id = {v})",
+        v = viewer
+    )
+}
+
+/// Whether either user has blocked the other, which rules out following,
+/// commenting on each other's content and replying to each other
+pub(crate) async fn is_blocked_between(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    a: &str,
+    b: &str,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(
+             SELECT 1 FROM user_blocks
+             WHERE (blocker_id = $1 AND blocked_id = $2) OR (blocker_id = $2 AND blocked_id = $1))"
+    )
+    .bind(a)
+    .bind(b)
+    .fetch_one(&mut *tx)
+    .await
+}
+
+/// Routes merged into `/api/users`
+pub fn user_block_routes() -> Router<Database> {
+    Router::new()
+        .route("/me/blocks", get(get_blocks))
+        .route("/me/mutes", get(get_mutes))
+        .route("/:id/block", post(block_user).delete(unblock_user))
+        .route("/:id/mute", post(mute_user).delete(unmute_user))
+}
+
+// Blocking also ends follows in both directions
+async fn block_user(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if id == claims.sub {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    ensure_user_exists(&mut tx, &id).await?;
+
+    sqlx::query("INSERT INTO user_blocks (blocker_id, blocked_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+        .bind(&claims.sub)
+        .bind(&id)
+        .execute(&mut tx)
+        .await
+        .map_err(|e| {
+            eprintln!("Error blocking user: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    sqlx::query(
+        "DELETE FROM follows
+         WHERE (follower_id = $1 AND followee_id = $2) OR (follower_id = $2 AND followee_id = $1)"
+    )
+    .bind(&claims.sub)
+    .bind(&id)
+    .execute(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "User blocked"
+    })))
+}
+
+async fn unblock_user(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    sqlx::query("DELETE FROM user_blocks WHERE blocker_id = $1 AND blocked_id = $2")
+        .bind(&claims.sub)
+        .bind(&id)
+        .execute(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "User unblocked"
+    })))
+}
+
+// Muting only hides the user from you; they are not told and can still interact
+async fn mute_user(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if id == claims.sub {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    ensure_user_exists(&mut tx, &id).await?;
+
+    sqlx::query("INSERT INTO user_mutes (muter_id, muted_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+        .bind(&claims.sub)
+        .bind(&id)
+        .execute(&mut tx)
+        .await
+        .map_err(|e| {
+            eprintln!("Error muting user: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "User muted"
+    })))
+}
+
+async fn unmute_user(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    sqlx::query("DELETE FROM user_mutes WHERE muter_id = $1 AND muted_id = $2")
+        .bind(&claims.sub)
+        .bind(&id)
+        .execute(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "User unmuted"
+    })))
+}
+
+async fn get_blocks(
+    State(db): State<Database>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let users = sqlx::query_as::<_, SilencedUser>(
+        "SELECT u.id, u.username, u.display_name AS name, u.avatar_url AS avatar, b.created_at AS since
+         FROM user_blocks b
+         JOIN users u ON u.id = b.blocked_id
+         WHERE b.blocker_id = $1
+         ORDER BY b.created_at DESC"
+    )
+    .bind(&claims.sub)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching blocked users: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": users
+    })))
+}
+
+async fn get_mutes(
+    State(db): State<Database>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let users = sqlx::query_as::<_, SilencedUser>(
+        "SELECT u.id, u.username, u.display_name AS name, u.avatar_url AS avatar, m.created_at AS since
+         FROM user_mutes m
+         JOIN users u ON u.id = m.muted_id
+         WHERE m.muter_id = $1
+         ORDER BY m.created_at DESC"
+    )
+    .bind(&claims.sub)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching muted users: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": users
+    })))
+}
+
+async fn ensure_user_exists(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: &str,
+) -> Result<(), StatusCode> {
+    let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if !exists {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(())
+}
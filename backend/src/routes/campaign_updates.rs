@@ -0,0 +1,224 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, put},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    notifications::{self, kind, NewNotification},
+    routes::{campaigns::ensure_campaign_owner, donations::is_campaign_backer},
+};
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CampaignUpdate {
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+    pub author_id: String,
+    pub title: String,
+    pub content: Option<String>,
+    pub image_url: Option<String>,
+    pub backers_only: bool,
+    /// Set when the caller may not read a backers-only update
+    #[sqlx(default)]
+    pub locked: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CampaignUpdateRequest {
+    pub title: String,
+    pub content: String,
+    pub image_url: Option<String>,
+    pub backers_only: Option<bool>,
+}
+
+const UPDATE_SELECT: &str = "SELECT id, campaign_id, author_id, title, content, image_url, backers_only, created_at, updated_at
+     FROM campaign_updates";
+
+/// Routes merged into `/api/campaigns`
+pub fn campaign_update_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id/updates", get(get_campaign_updates).post(create_campaign_update))
+        .route("/:id/updates/:update_id", put(edit_campaign_update).delete(delete_campaign_update))
+}
+
+async fn get_campaign_updates(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+    claims: Option<Claims>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let updates = sqlx::query_as::<_, CampaignUpdate>(&format!(
+        "{} WHERE campaign_id = $1 ORDER BY created_at DESC",
+        UPDATE_SELECT
+    ))
+    .bind(campaign_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching campaign updates: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let can_read_private = match &claims {
+        Some(claims) if updates.iter().any(|u| u.backers_only) => can_read_backer_updates(&db, campaign_id, &claims.sub).await?,
+        _ => false,
+    };
+
+    // Backers-only updates stay listed so visitors know they exist, without their content
+    let updates: Vec<CampaignUpdate> = updates
+        .into_iter()
+        .map(|mut update| {
+            if update.backers_only && !can_read_private {
+                update.content = None;
+                update.image_url = None;
+                update.locked = true;
+            }
+            update
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": updates
+    })))
+}
+
+async fn create_campaign_update(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<CampaignUpdateRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+    validate_update(&payload)?;
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let update = sqlx::query_as::<_, CampaignUpdate>(
+        "INSERT INTO campaign_updates (campaign_id, author_id, title, content, image_url, backers_only)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         RETURNING id, campaign_id, author_id, title, content, image_url, backers_only, created_at, updated_at"
+    )
+    .bind(campaign_id)
+    .bind(&claims.sub)
+    .bind(payload.title.trim())
+    .bind(&payload.content)
+    .bind(&payload.image_url)
+    .bind(payload.backers_only.unwrap_or(false))
+    .fetch_one(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating campaign update: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let slug = sqlx::query_scalar::<_, String>("SELECT slug FROM campaigns WHERE id = $1")
+        .bind(campaign_id)
+        .fetch_one(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    notifications::notify_campaign_backers(&mut tx, campaign_id, &NewNotification {
+        kind: kind::CAMPAIGN_UPDATE,
+        title: format!("New update: {}", update.title),
+        message: None,
+        link: Some(format!("/campaigns/{}#update-{}", slug, update.id)),
+        actor_id: Some(&claims.sub),
+    })
+    .await
+    .map_err(|e| {
+        eprintln!("Error notifying backers: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": update
+    })))
+}
+
+async fn edit_campaign_update(
+    State(db): State<Database>,
+    Path((campaign_id, update_id)): Path<(Uuid, Uuid)>,
+    claims: Claims,
+    Json(payload): Json<CampaignUpdateRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+    validate_update(&payload)?;
+
+    let update = sqlx::query_as::<_, CampaignUpdate>(
+        "UPDATE campaign_updates
+         SET title = $3, content = $4, image_url = $5, backers_only = COALESCE($6, backers_only), updated_at = NOW()
+         WHERE id = $1 AND campaign_id = $2
+         RETURNING id, campaign_id, author_id, title, content, image_url, backers_only, created_at, updated_at"
+    )
+    .bind(update_id)
+    .bind(campaign_id)
+    .bind(payload.title.trim())
+    .bind(&payload.content)
+    .bind(&payload.image_url)
+    .bind(payload.backers_only)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": update
+    })))
+}
+
+async fn delete_campaign_update(
+    State(db): State<Database>,
+    Path((campaign_id, update_id)): Path<(Uuid, Uuid)>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+
+    let result = sqlx::query("DELETE FROM campaign_updates WHERE id = $1 AND campaign_id = $2")
+        .bind(update_id)
+        .bind(campaign_id)
+        .execute(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Update deleted"
+    })))
+}
+
+async fn can_read_backer_updates(db: &Database, campaign_id: Uuid, user_id: &str) -> Result<bool, StatusCode> {
+    if ensure_campaign_owner(db, campaign_id, user_id).await.is_ok() {
+        return Ok(true);
+    }
+
+    is_campaign_backer(db, campaign_id, user_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+fn validate_update(payload: &CampaignUpdateRequest) -> Result<(), StatusCode> {
+    if payload.title.trim().is_empty() || payload.content.trim().is_empty() {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    Ok(())
+}
@@ -1,16 +1,31 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    response::Json,
-    routing::{get, post},
+    response::{IntoResponse, Json, Redirect, Response},
+    routing::{get, post, put},
     Router,
 };
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use sqlx::Row;
+use std::collections::HashSet;
 
-use crate::database::Database;
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::{campaign_category, campaign_status, donation_status, funding_model},
+    notifications::{self, kind, NewNotification},
+    routes::{
+        products::FeaturedRequest,
+        campaign_updates::campaign_update_routes,
+        comments::campaign_comment_routes,
+        donations::campaign_donation_routes,
+        rewards::{campaign_reward_routes, list_rewards},
+        stretch_goals::{campaign_stretch_goal_routes, list_stretch_goals},
+    },
+    validation::{is_currency_code, is_http_url, nullable, FieldErrors, RequestError},
+};
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Campaign {
@@ -19,132 +34,446 @@ pub struct Campaign {
     pub description: String,
     pub goal_amount: f64,
     pub current_amount: Option<f64>,
+    pub currency: String,
     pub status: String,
     pub slug: String,
+    pub category: Option<String>,
+    pub cover_image: Option<String>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub featured: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct TrendingCampaign {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub campaign: Campaign,
+    #[serde(rename = "recentAmount")]
+    pub recent_amount: f64,
+    #[serde(rename = "recentBackers")]
+    pub recent_backers: i64,
+}
+
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CampaignQuery {
     pub page: Option<u32>,
     pub limit: Option<u32>,
+    /// Search text, required by `/search`
+    pub q: Option<String>,
+    pub category: Option<String>,
+    pub status: Option<String>,
+    /// Funding percentage bounds, e.g. `minFunded=100` for campaigns past their goal
+    pub min_funded: Option<f64>,
+    pub max_funded: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrendingQuery {
+    pub limit: Option<u32>,
+}
+
+// Accepts the frontend's camelCase fields as well as the original snake_case ones
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCampaignRequest {
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub story: Option<String>,
+    #[serde(alias = "goal", alias = "goal_amount")]
+    pub goal_amount: Option<f64>,
+    #[serde(alias = "imageUrl", alias = "cover_image")]
+    pub cover_image: Option<String>,
+    #[serde(alias = "video_url")]
+    pub video_url: Option<String>,
+    pub category: Option<String>,
+    #[serde(alias = "end_date")]
+    pub end_date: Option<DateTime<Utc>>,
+    #[serde(alias = "funding_model")]
+    pub funding_model: Option<String>,
+    /// ISO code the goal and every donation are in; defaults to USD
+    pub currency: Option<String>,
+}
+
+/// Fields left out keep their current value; optional fields sent as `null` are cleared
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCampaignRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub story: Option<Option<String>>,
+    #[serde(alias = "goal", alias = "goal_amount")]
+    pub goal_amount: Option<f64>,
+    #[serde(default, alias = "imageUrl", alias = "cover_image", deserialize_with = "nullable")]
+    pub cover_image: Option<Option<String>>,
+    #[serde(default, alias = "video_url", deserialize_with = "nullable")]
+    pub video_url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub category: Option<Option<String>>,
+    #[serde(default, alias = "end_date", deserialize_with = "nullable")]
+    pub end_date: Option<Option<DateTime<Utc>>>,
+    #[serde(alias = "funding_model")]
+    pub funding_model: Option<String>,
+}
+
+impl CreateCampaignRequest {
+    fn validate(&self) -> Result<(), RequestError> {
+        let mut errors = FieldErrors::default();
+
+        check_text(&mut errors, "title", &self.title, MAX_TITLE_LENGTH);
+        check_text(&mut errors, "description", &self.description, MAX_DESCRIPTION_LENGTH);
+        match self.goal_amount {
+            Some(goal_amount) => check_goal_amount(&mut errors, goal_amount),
+            None => errors.add("goalAmount", "is required"),
+        }
+        check_details(
+            &mut errors,
+            self.cover_image.as_deref(),
+            self.video_url.as_deref(),
+            self.category.as_deref(),
+            self.end_date,
+        );
+        check_funding_model(
+            &mut errors,
+            self.funding_model.as_deref().unwrap_or(funding_model::KEEP_IT_ALL),
+            self.end_date,
+        );
+        if self.currency.as_deref().is_some_and(|currency| !is_currency_code(currency)) {
+            errors.add("currency", "must be a three-letter ISO currency code");
+        }
+
+        errors.into_result()
+    }
+}
+
+impl UpdateCampaignRequest {
+    fn validate(&self) -> Result<(), RequestError> {
+        let mut errors = FieldErrors::default();
+
+        if let Some(title) = &self.title {
+            check_text(&mut errors, "title", title, MAX_TITLE_LENGTH);
+        }
+        if let Some(description) = &self.description {
+            check_text(&mut errors, "description", description, MAX_DESCRIPTION_LENGTH);
+        }
+        if let Some(goal_amount) = self.goal_amount {
+            check_goal_amount(&mut errors, goal_amount);
+        }
+        check_details(
+            &mut errors,
+            self.cover_image.as_ref().and_then(Option::as_deref),
+            self.video_url.as_ref().and_then(Option::as_deref),
+            self.category.as_ref().and_then(Option::as_deref),
+            self.end_date.flatten(),
+        );
+
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExtendCampaignRequest {
+    pub end_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSlugRequest {
+    pub slug: String,
+}
+
+const CAMPAIGN_COLUMNS: &str = "c.id, c.title, c.description, c.goal_amount, c.current_amount, c.currency, c.status, c.slug,
+        c.category, c.cover_image, c.end_date, c.featured_at IS NOT NULL AS featured, c.created_at, c.updated_at";
+
+// Shared by listing and search; every filter is optional except the status
+const CAMPAIGN_FILTER: &str = "c.status = $1
+       AND ($2::text IS NULL OR UPPER(c.category) = UPPER($2))
+       AND ($3::float8 IS NULL OR COALESCE(c.current_amount, 0) * 100 >= $3 * c.goal_amount)
+       AND ($4::float8 IS NULL OR COALESCE(c.current_amount, 0) * 100 <= $4 * c.goal_amount)
+       AND ($5::text IS NULL OR c.search_vector @@ websearch_to_tsquery('english', $5))";
+
+const MAX_TITLE_LENGTH: usize = 255;
+const MAX_DESCRIPTION_LENGTH: usize = 1000;
+const MAX_SLUG_LENGTH: usize = 80;
+const SLUG_ATTEMPTS: usize = 3;
+
+// The fields lifecycle transitions need, locked for the duration of a transaction
+#[derive(Debug, sqlx::FromRow)]
+struct CampaignState {
+    id: Uuid,
+    title: String,
+    slug: String,
+    creator_id: String,
+    status: String,
+    goal_amount: f64,
+    end_date: Option<DateTime<Utc>>,
+    funding_model: String,
 }
 
 pub fn campaign_routes() -> Router<Database> {
     Router::new()
         .route("/", get(get_campaigns))
         .route("/", post(create_campaign))
-        .route("/:slug", get(get_campaign_by_slug))
+        .route("/search", get(search_campaigns))
+        .route("/trending", get(get_trending_campaigns))
+        .route("/featured", get(get_featured_campaigns))
+        // Campaigns are addressable by slug or id
+        .route("/:id", get(get_campaign_by_slug).put(update_campaign).delete(delete_campaign))
+        .route("/:id/publish", post(publish_campaign))
+        .route("/:id/cancel", post(cancel_campaign))
+        .route("/:id/extend", put(extend_campaign))
+        .route("/:id/slug", put(update_campaign_slug))
+        .route("/:id/featured", put(set_campaign_featured))
+        .merge(campaign_donation_routes())
+        .merge(campaign_reward_routes())
+        .merge(campaign_stretch_goal_routes())
+        .merge(campaign_update_routes())
+        .merge(campaign_comment_routes())
 }
 
 async fn get_campaigns(
     State(db): State<Database>,
     Query(params): Query<CampaignQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    let page = params.page.unwrap_or(1);
-    let limit = params.limit.unwrap_or(12);
+    list_campaigns(&db, &params, "c.created_at DESC").await
+}
+
+async fn search_campaigns(
+    State(db): State<Database>,
+    Query(params): Query<CampaignQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if params.q.as_deref().is_none_or(|q| q.trim().is_empty()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    list_campaigns(
+        &db,
+        &params,
+        "ts_rank(c.search_vector, websearch_to_tsquery('english', $5)) DESC, c.created_at DESC",
+    )
+    .await
+}
+
+async fn list_campaigns(
+    db: &Database,
+    params: &CampaignQuery,
+    order_by: &str,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(12).clamp(1, 100);
     let offset = (page - 1) * limit;
 
-    // Use simple SQL query with snake_case table and column names
-    let query = "SELECT id, title, description, goal_amount, current_amount, status, slug, created_at, updated_at FROM campaigns ORDER BY created_at DESC LIMIT $1 OFFSET $2";
-    
-    match sqlx::query_as::<_, Campaign>(query)
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(&db.pool)
-        .await
-    {
-        Ok(campaigns) => {
-            // Frontend'in beklediği format
-            let response = serde_json::json!({
-                "success": true,
-                "data": campaigns,
-                "pagination": {
-                    "page": page,
-                    "limit": limit,
-                    "total": campaigns.len(),
-                    "pages": 1
-                }
-            });
-            Ok(Json(response))
-        },
-        Err(e) => {
-            tracing::error!("Failed to fetch campaigns: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+    // Drafts and cancelled campaigns are never listed publicly
+    let status = params.status.as_deref().unwrap_or(campaign_status::ACTIVE);
+    if ![campaign_status::ACTIVE, campaign_status::FUNDED, campaign_status::ENDED].contains(&status) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let category = params.category.as_deref().filter(|c| !c.is_empty());
+    let search = params.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+
+    let campaigns = sqlx::query_as::<_, Campaign>(&format!(
+        "SELECT {} FROM campaigns c WHERE {} ORDER BY {} LIMIT $6 OFFSET $7",
+        CAMPAIGN_COLUMNS, CAMPAIGN_FILTER, order_by
+    ))
+    .bind(status)
+    .bind(category)
+    .bind(params.min_funded)
+    .bind(params.max_funded)
+    .bind(search)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch campaigns: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let total = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM campaigns c WHERE {}",
+        CAMPAIGN_FILTER
+    ))
+    .bind(status)
+    .bind(category)
+    .bind(params.min_funded)
+    .bind(params.max_funded)
+    .bind(search)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": campaigns,
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": total,
+            "pages": ((total as f64) / (limit as f64)).ceil() as u32
         }
+    })))
+}
+
+// Ranked by how much active campaigns raised over the last three days
+async fn get_trending_campaigns(
+    State(db): State<Database>,
+    Query(params): Query<TrendingQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let limit = params.limit.unwrap_or(6).clamp(1, 50);
+
+    let campaigns = sqlx::query_as::<_, TrendingCampaign>(&format!(
+        "SELECT {}, v.recent_amount, v.recent_backers
+         FROM campaigns c
+         JOIN (
+             SELECT campaign_id, SUM(amount) AS recent_amount, COUNT(DISTINCT user_id) AS recent_backers
+             FROM donations
+             WHERE status = ANY($2) AND created_at > NOW() - INTERVAL '3 days'
+             GROUP BY campaign_id
+         ) v ON v.campaign_id = c.id
+         WHERE c.status = $1
+         ORDER BY v.recent_amount DESC, v.recent_backers DESC
+         LIMIT $3",
+        CAMPAIGN_COLUMNS
+    ))
+    .bind(campaign_status::ACTIVE)
+    .bind(donation_status::BACKED)
+    .bind(limit as i64)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching trending campaigns: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": campaigns
+    })))
+}
+
+async fn get_featured_campaigns(
+    State(db): State<Database>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let campaigns = sqlx::query_as::<_, Campaign>(&format!(
+        "SELECT {} FROM campaigns c WHERE c.status = $1 AND c.featured_at IS NOT NULL ORDER BY c.featured_at DESC LIMIT 12",
+        CAMPAIGN_COLUMNS
+    ))
+    .bind(campaign_status::ACTIVE)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching featured campaigns: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": campaigns
+    })))
+}
+
+// Featured campaigns are curated by admins, not their creators
+async fn set_campaign_featured(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<FeaturedRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if !db.is_admin(&claims.sub).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? {
+        return Err(StatusCode::FORBIDDEN);
     }
+
+    let campaign = sqlx::query_as::<_, Campaign>(&format!(
+        "WITH c AS (
+             UPDATE campaigns
+             SET featured_at = CASE WHEN $2 THEN COALESCE(featured_at, NOW()) END, updated_at = NOW()
+             WHERE id = $1
+             RETURNING *
+         )
+         SELECT {} FROM c",
+        CAMPAIGN_COLUMNS
+    ))
+    .bind(id)
+    .bind(payload.featured)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error updating featured flag: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": campaign
+    })))
 }
 
 async fn create_campaign(
     State(db): State<Database>,
     claims: crate::auth::Claims,
-    Json(payload): Json<serde_json::Value>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+    Json(payload): Json<CreateCampaignRequest>,
+) -> Result<Json<serde_json::Value>, RequestError> {
     println!("🔄 Creating campaign for user: {}", claims.sub);
-    println!("📝 Campaign payload: {}", serde_json::to_string(&payload).unwrap_or("Failed to serialize".to_string()));
-    
-    // Extract values from payload
-    let title = payload.get("title")
-        .and_then(|v| v.as_str())
-        .unwrap_or("New Campaign");
-    
-    let description = payload.get("description")
-        .and_then(|v| v.as_str())
-        .unwrap_or("Campaign description");
-    
-    let story = payload.get("story")
-        .and_then(|v| v.as_str())
-        .unwrap_or(description);
-    
-    let goal_amount = payload.get("goal_amount")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(1000.0);
-    
-    let cover_image = payload.get("cover_image")
-        .and_then(|v| v.as_str())
-        .unwrap_or("https://images.unsplash.com/photo-1488521787991-ed7bbaae773c?w=1200&q=80");
-    
-    let video_url = payload.get("video_url")
-        .and_then(|v| v.as_str());
-    
-    let category = payload.get("category")
-        .and_then(|v| v.as_str())
-        .unwrap_or("OTHER");
-    
-    let end_date = payload.get("end_date")
-        .and_then(|v| v.as_str());
-    
-    // Generate a unique slug from title
-    let slug = title
-        .to_lowercase()
-        .replace(" ", "-")
-        .replace("'", "")
-        .replace("\"", "")
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '-')
-        .collect::<String>();
+
+    payload.validate()?;
+
+    let title = payload.title.trim();
+    let description = payload.description.trim();
+    let story = payload.story.as_deref();
+    let goal_amount = payload.goal_amount.unwrap_or_default();
+    let cover_image = payload.cover_image.as_deref();
+    let video_url = payload.video_url.as_deref();
+    let category = payload.category.as_deref().unwrap_or(campaign_category::OTHER);
+    let end_date = payload.end_date;
+    let model = payload.funding_model.as_deref().unwrap_or(funding_model::KEEP_IT_ALL);
+    let currency = payload.currency.as_deref().unwrap_or("USD").to_uppercase();
     
-    // Store campaign in database with all fields
+    // Titles can collide, so retry with the next free suffix if another
+    // campaign claims the same slug between the lookup and the insert
+    let base_slug = Some(slugify(title))
+        .filter(|slug| is_valid_slug(slug))
+        .unwrap_or_else(|| "campaign".to_string());
     let campaign_id = uuid::Uuid::new_v4();
-    let result = sqlx::query(
-        "INSERT INTO campaigns (id, title, description, story, goal_amount, slug, status, creator_id, cover_image, video_url, category, end_date, created_at, updated_at) 
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW(), NOW())"
-    )
-    .bind(campaign_id)
-    .bind(title)
-    .bind(description)
-    .bind(story)
-    .bind(goal_amount)
-    .bind(&slug)
-    .bind("DRAFT")
-    .bind(&claims.sub)
-    .bind(cover_image)
-    .bind(video_url)
-    .bind(category)
-    .bind(end_date)
-    .execute(&db.pool)
-    .await;
+    let mut slug = String::new();
+    let mut result = Ok(());
+    for _ in 0..SLUG_ATTEMPTS {
+        slug = available_slug(&db, &base_slug)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        // Store campaign in database with all fields
+        result = sqlx::query(
+            "INSERT INTO campaigns (id, title, description, story, goal_amount, slug, status, creator_id, cover_image, video_url, category, end_date, funding_model, currency, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12::timestamptz, $13, $14, NOW(), NOW())"
+        )
+        .bind(campaign_id)
+        .bind(title)
+        .bind(description)
+        .bind(story)
+        .bind(goal_amount)
+        .bind(&slug)
+        .bind(campaign_status::DRAFT)
+        .bind(&claims.sub)
+        .bind(cover_image)
+        .bind(video_url)
+        .bind(category)
+        .bind(end_date)
+        .bind(model)
+        .bind(&currency)
+        .execute(&db.pool)
+        .await
+        .map(|_| ());
+
+        if !matches!(&result, Err(e) if is_slug_conflict(e)) {
+            break;
+        }
+    }
     
     match result {
         Ok(_) => {
@@ -157,14 +486,16 @@ async fn create_campaign(
                     "description": description,
                     "goal_amount": goal_amount,
                     "current_amount": 0.0,
-                    "status": "DRAFT"
+                    "currency": currency,
+                    "status": campaign_status::DRAFT,
+                    "funding_model": model
                 }
             });
             Ok(Json(response))
         }
         Err(e) => {
             eprintln!("Error creating campaign: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(StatusCode::INTERNAL_SERVER_ERROR.into())
         }
     }
 }
@@ -172,20 +503,39 @@ async fn create_campaign(
 async fn get_campaign_by_slug(
     State(db): State<Database>,
     Path(slug): Path<String>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+    claims: Option<Claims>,
+) -> Result<Response, StatusCode> {
     // Query campaign from database by slug with all fields
     let campaign = sqlx::query(
         "SELECT c.id, c.title, c.description, c.goal_amount, c.current_amount, c.status, c.slug, c.created_at, c.updated_at,
-                c.cover_image, c.video_url, c.story, c.category, c.end_date,
-                u.id as creator_id, u.username, u.display_name, u.avatar_url, u.bio
+                c.cover_image, c.video_url, c.story, c.category, c.end_date, c.funding_model, c.currency,
+                u.id as creator_id, u.username, u.display_name, u.avatar_url, u.bio,
+                (SELECT COUNT(DISTINCT d.user_id) FROM donations d
+                 WHERE d.campaign_id = c.id AND d.status = ANY($2)) AS backers
          FROM campaigns c
          LEFT JOIN users u ON c.creator_id = u.id
-         WHERE c.slug = $1"
+         WHERE c.slug = $1 OR c.id::text = $1"
     )
     .bind(&slug)
+    .bind(donation_status::BACKED)
     .fetch_one(&db.pool)
     .await;
     
+    // Old slugs of renamed campaigns redirect to the current one
+    if matches!(campaign, Err(sqlx::Error::RowNotFound)) {
+        let current_slug = sqlx::query_scalar::<_, String>(
+            "SELECT c.slug FROM slug_history h JOIN campaigns c ON c.id = h.campaign_id WHERE h.slug = $1"
+        )
+        .bind(&slug)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        if let Some(current_slug) = current_slug {
+            return Ok(Redirect::permanent(&format!("/api/campaigns/{}", current_slug)).into_response());
+        }
+    }
+
     match campaign {
         Ok(row) => {
             let id: Uuid = row.get("id");
@@ -201,13 +551,25 @@ async fn get_campaign_by_slug(
             let story: Option<String> = row.get("story");
             let category: Option<String> = row.get("category");
             let end_date: Option<DateTime<Utc>> = row.get("end_date");
+            let campaign_funding_model: String = row.get("funding_model");
+            let currency: String = row.get("currency");
             
             // Creator info
-            let creator_id: Option<Uuid> = row.get("creator_id");
+            let creator_id: Option<String> = row.get("creator_id");
             let username: Option<String> = row.get("username");
             let display_name: Option<String> = row.get("display_name");
             let avatar_url: Option<String> = row.get("avatar_url");
             let bio: Option<String> = row.get("bio");
+            let backers: i64 = row.get("backers");
+
+            // Drafts are only visible to their creator
+            let is_creator = matches!((&claims, &creator_id), (Some(c), Some(id)) if &c.sub == id);
+            if status == campaign_status::DRAFT && !is_creator {
+                return Err(StatusCode::NOT_FOUND);
+            }
+
+            let rewards = list_rewards(&db, id).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let stretch_goals = list_stretch_goals(&db, id).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
             
             let response = serde_json::json!({
                 "success": true,
@@ -220,13 +582,15 @@ async fn get_campaign_by_slug(
                     "goal": goal_amount,
                     "goalAmount": goal_amount,
                     "currentAmount": current_amount.unwrap_or(0.0),
+                    "currency": currency,
                     "status": status,
                     "category": category.unwrap_or("OTHER".to_string()),
                     "imageUrl": cover_image.unwrap_or("https://images.unsplash.com/photo-1488521787991-ed7bbaae773c?w=1200&q=80".to_string()),
                     "videoUrl": video_url,
                     "endDate": end_date,
+                    "fundingModel": campaign_funding_model,
                     "createdAt": created_at,
-                    "creator": creator_id.map(|_| {
+                    "creator": creator_id.as_ref().map(|_| {
                         serde_json::json!({
                             "id": creator_id,
                             "username": username,
@@ -237,10 +601,12 @@ async fn get_campaign_by_slug(
                         })
                     }),
                     "creatorId": creator_id,
-                    "backers": 0
+                    "backers": backers,
+                    "rewards": rewards,
+                    "stretchGoals": stretch_goals
                 }
             });
-            Ok(Json(response))
+            Ok(Json(response).into_response())
         }
         Err(e) => {
             eprintln!("Error fetching campaign: {:?}", e);
@@ -248,3 +614,570 @@ async fn get_campaign_by_slug(
         }
     }
 }
+
+async fn update_campaign(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<UpdateCampaignRequest>,
+) -> Result<Json<serde_json::Value>, RequestError> {
+    payload.validate()?;
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let campaign = lock_owned_campaign(&mut tx, id, &claims.sub).await?;
+    ensure_no_backers(&mut tx, id).await?;
+
+    if ![campaign_status::DRAFT, campaign_status::ACTIVE].contains(&campaign.status.as_str()) {
+        return Err(StatusCode::CONFLICT.into());
+    }
+
+    // The deadline rule depends on the stored values for fields left out
+    let mut errors = FieldErrors::default();
+    check_funding_model(
+        &mut errors,
+        payload.funding_model.as_deref().unwrap_or(&campaign.funding_model),
+        payload.end_date.unwrap_or(campaign.end_date),
+    );
+    errors.into_result()?;
+
+    sqlx::query(
+        "UPDATE campaigns SET
+            title = COALESCE($2, title),
+            description = COALESCE($3, description),
+            goal_amount = COALESCE($4, goal_amount),
+            funding_model = COALESCE($5, funding_model),
+            story = CASE WHEN $6 THEN $7 ELSE story END,
+            cover_image = CASE WHEN $8 THEN $9 ELSE cover_image END,
+            video_url = CASE WHEN $10 THEN $11 ELSE video_url END,
+            category = CASE WHEN $12 THEN $13 ELSE category END,
+            end_date = CASE WHEN $14 THEN $15 ELSE end_date END,
+            updated_at = NOW()
+         WHERE id = $1"
+    )
+    .bind(id)
+    .bind(payload.title.as_deref().map(str::trim))
+    .bind(payload.description.as_deref().map(str::trim))
+    .bind(payload.goal_amount)
+    .bind(&payload.funding_model)
+    .bind(payload.story.is_some())
+    .bind(payload.story.as_ref().and_then(Option::as_deref))
+    .bind(payload.cover_image.is_some())
+    .bind(payload.cover_image.as_ref().and_then(Option::as_deref))
+    .bind(payload.video_url.is_some())
+    .bind(payload.video_url.as_ref().and_then(Option::as_deref))
+    .bind(payload.category.is_some())
+    .bind(payload.category.as_ref().and_then(Option::as_deref))
+    .bind(payload.end_date.is_some())
+    .bind(payload.end_date.flatten())
+    .execute(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error updating campaign: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let campaign = sqlx::query_as::<_, Campaign>(&format!(
+        "SELECT {} FROM campaigns c WHERE c.id = $1",
+        CAMPAIGN_COLUMNS
+    ))
+    .bind(id)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": campaign
+    })))
+}
+
+async fn delete_campaign(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    lock_owned_campaign(&mut tx, id, &claims.sub).await?;
+    ensure_no_backers(&mut tx, id).await?;
+
+    sqlx::query("DELETE FROM campaigns WHERE id = $1")
+        .bind(id)
+        .execute(&mut tx)
+        .await
+        .map_err(|e| {
+            eprintln!("Error deleting campaign: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Campaign deleted"
+    })))
+}
+
+async fn publish_campaign(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let campaign = lock_owned_campaign(&mut tx, id, &claims.sub).await?;
+
+    if !campaign_status::can_transition(&campaign.status, campaign_status::ACTIVE) {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    if campaign.goal_amount <= 0.0 || campaign.end_date.is_some_and(|end| end <= Utc::now()) {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    if campaign.funding_model == funding_model::ALL_OR_NOTHING && campaign.end_date.is_none() {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    sqlx::query("UPDATE campaigns SET status = $2, published_at = NOW(), updated_at = NOW() WHERE id = $1")
+        .bind(id)
+        .bind(campaign_status::ACTIVE)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(lifecycle_response(&campaign, campaign_status::ACTIVE, campaign.end_date)))
+}
+
+async fn cancel_campaign(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let campaign = lock_owned_campaign(&mut tx, id, &claims.sub).await?;
+
+    if !campaign_status::can_transition(&campaign.status, campaign_status::CANCELLED) {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    // Captured money has to be refunded before the campaign can be cancelled.
+    // Uncaptured pledges are released by the settlement job once it is closed.
+    let has_paid_backers = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM donations WHERE campaign_id = $1 AND (status = ANY($2) OR status = $3))"
+    )
+    .bind(id)
+    .bind(donation_status::SETTLED)
+    .bind(donation_status::PENDING)
+    .fetch_one(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if has_paid_backers {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    sqlx::query("UPDATE campaigns SET status = $2, closed_at = NOW(), updated_at = NOW() WHERE id = $1")
+        .bind(id)
+        .bind(campaign_status::CANCELLED)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    notifications::notify_campaign_backers(&mut tx, id, &NewNotification {
+        kind: kind::CAMPAIGN_CANCELLED,
+        title: format!("{} was cancelled", campaign.title),
+        message: Some("The creator has cancelled this campaign.".to_string()),
+        link: Some(format!("/campaigns/{}", campaign.slug)),
+        actor_id: Some(&claims.sub),
+    })
+    .await
+    .map_err(|e| {
+        eprintln!("Error notifying backers: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(lifecycle_response(&campaign, campaign_status::CANCELLED, campaign.end_date)))
+}
+
+async fn extend_campaign(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<ExtendCampaignRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let campaign = lock_owned_campaign(&mut tx, id, &claims.sub).await?;
+
+    if campaign.status != campaign_status::ACTIVE {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    // An extension can only push the deadline further out
+    let extends = campaign.end_date.is_none_or(|end| payload.end_date > end);
+    if !extends || payload.end_date <= Utc::now() {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    sqlx::query("UPDATE campaigns SET end_date = $2, updated_at = NOW() WHERE id = $1")
+        .bind(id)
+        .bind(payload.end_date)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(lifecycle_response(&campaign, &campaign.status, Some(payload.end_date))))
+}
+
+async fn update_campaign_slug(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<UpdateSlugRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let slug = slugify(&payload.slug);
+    if !is_valid_slug(&slug) {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let campaign = lock_owned_campaign(&mut tx, id, &claims.sub).await?;
+
+    if campaign.slug != slug {
+        // Another campaign's current or former slug stays theirs
+        let taken = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM campaigns WHERE slug = $1 AND id <> $2)
+                 OR EXISTS(SELECT 1 FROM slug_history WHERE slug = $1 AND campaign_id <> $2)"
+        )
+        .bind(&slug)
+        .bind(id)
+        .fetch_one(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        if taken {
+            return Err(StatusCode::CONFLICT);
+        }
+
+        // Moving back to an earlier slug takes it out of the history
+        sqlx::query("DELETE FROM slug_history WHERE slug = $1")
+            .bind(&slug)
+            .execute(&mut tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        sqlx::query("INSERT INTO slug_history (slug, campaign_id) VALUES ($1, $2) ON CONFLICT (slug) DO NOTHING")
+            .bind(&campaign.slug)
+            .bind(id)
+            .execute(&mut tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        sqlx::query("UPDATE campaigns SET slug = $2, updated_at = NOW() WHERE id = $1")
+            .bind(id)
+            .bind(&slug)
+            .execute(&mut tx)
+            .await
+            .map_err(|e| {
+                if is_slug_conflict(&e) {
+                    StatusCode::CONFLICT
+                } else {
+                    eprintln!("Error renaming campaign: {:?}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+            })?;
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            "id": campaign.id,
+            "slug": slug,
+            "previousSlug": campaign.slug
+        }
+    })))
+}
+
+// Once money has moved, the campaign's terms are fixed and its payment records
+// must stay attached. Only failed payment attempts don't count.
+async fn ensure_no_backers(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, campaign_id: Uuid) -> Result<(), StatusCode> {
+    let has_backers = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM donations WHERE campaign_id = $1 AND status <> $2)"
+    )
+    .bind(campaign_id)
+    .bind(donation_status::FAILED)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if has_backers {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(())
+}
+
+fn check_text(errors: &mut FieldErrors, field: &'static str, value: &str, max_length: usize) {
+    let length = value.trim().chars().count();
+    if length == 0 {
+        errors.add(field, "is required");
+    } else if length > max_length {
+        errors.add(field, format!("must be at most {} characters", max_length));
+    }
+}
+
+fn check_goal_amount(errors: &mut FieldErrors, goal_amount: f64) {
+    if !goal_amount.is_finite() || goal_amount <= 0.0 {
+        errors.add("goalAmount", "must be greater than 0");
+    }
+}
+
+fn check_details(
+    errors: &mut FieldErrors,
+    cover_image: Option<&str>,
+    video_url: Option<&str>,
+    category: Option<&str>,
+    end_date: Option<DateTime<Utc>>,
+) {
+    if cover_image.is_some_and(|url| !is_http_url(url)) {
+        errors.add("coverImage", "must be an http(s) URL");
+    }
+    if video_url.is_some_and(|url| !is_http_url(url)) {
+        errors.add("videoUrl", "must be an http(s) URL");
+    }
+    if category.is_some_and(|category| !campaign_category::is_valid(category)) {
+        errors.add("category", format!("must be one of {}", campaign_category::ALL.join(", ")));
+    }
+    if end_date.is_some_and(|end| end <= Utc::now()) {
+        errors.add("endDate", "must be in the future");
+    }
+}
+
+// All-or-nothing pledges are settled at the deadline, so one is required
+fn check_funding_model(errors: &mut FieldErrors, model: &str, end_date: Option<DateTime<Utc>>) {
+    if !funding_model::is_valid(model) {
+        errors.add("fundingModel", format!("must be {} or {}", funding_model::KEEP_IT_ALL, funding_model::ALL_OR_NOTHING));
+    } else if model == funding_model::ALL_OR_NOTHING && end_date.is_none() {
+        errors.add("endDate", "is required for all-or-nothing campaigns");
+    }
+}
+
+/// Lowercase ASCII slug, transliterating non-Latin scripts ("Café Ünïcode" → "cafe-unicode")
+pub(crate) fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in deunicode::deunicode(text).to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if c != '\'' && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    // Only ASCII remains, so truncating on a byte index is safe
+    slug.truncate(MAX_SLUG_LENGTH);
+    slug.trim_end_matches('-').to_string()
+}
+
+// A slug that parses as a UUID would shadow lookups by id
+fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty() && Uuid::parse_str(slug).is_err()
+}
+
+/// The first of `base`, `base-2`, `base-3`, ... not used by any campaign,
+/// including slugs campaigns have since renamed away from
+async fn available_slug(db: &Database, base: &str) -> Result<String, sqlx::Error> {
+    let taken: HashSet<String> = sqlx::query_scalar::<_, String>(
+        "SELECT slug FROM campaigns WHERE slug = $1 OR slug LIKE $1 || '-%'
+         UNION
+         SELECT slug FROM slug_history WHERE slug = $1 OR slug LIKE $1 || '-%'"
+    )
+    .bind(base)
+    .fetch_all(&db.pool)
+    .await?
+    .into_iter()
+    .collect();
+
+    if !taken.contains(base) {
+        return Ok(base.to_string());
+    }
+
+    Ok((2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("suffixes are unbounded"))
+}
+
+fn is_slug_conflict(e: &sqlx::Error) -> bool {
+    e.as_database_error()
+        .and_then(|db_error| db_error.constraint())
+        .is_some_and(|constraint| constraint == "campaigns_slug_key")
+}
+
+async fn lock_owned_campaign(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: Uuid,
+    user_id: &str,
+) -> Result<CampaignState, StatusCode> {
+    let campaign = sqlx::query_as::<_, CampaignState>(
+        "SELECT id, title, slug, creator_id, status, goal_amount, end_date, funding_model FROM campaigns WHERE id = $1 FOR UPDATE"
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    if campaign.creator_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(campaign)
+}
+
+pub(crate) async fn ensure_campaign_owner(db: &Database, campaign_id: Uuid, user_id: &str) -> Result<(), StatusCode> {
+    let creator_id = sqlx::query_scalar::<_, String>("SELECT creator_id FROM campaigns WHERE id = $1")
+        .bind(campaign_id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if creator_id == user_id {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+fn lifecycle_response(campaign: &CampaignState, status: &str, end_date: Option<DateTime<Utc>>) -> serde_json::Value {
+    serde_json::json!({
+        "success": true,
+        "data": {
+            "id": campaign.id,
+            "slug": campaign.slug,
+            "status": status,
+            "endDate": end_date
+        }
+    })
+}
+
+/// Close active campaigns whose `end_date` has passed, marking them funded or
+/// ended depending on whether the goal was met, and notify their backers.
+/// Run periodically from the background job loop.
+pub(crate) async fn close_expired_campaigns(db: &Database) -> Result<usize, sqlx::Error> {
+    let mut tx = db.pool.begin().await?;
+
+    // SKIP LOCKED keeps concurrent runners (or a racing cancel) from double-closing
+    let closed = sqlx::query_as::<_, (Uuid, String, String, String, String)>(
+        "UPDATE campaigns
+         SET status = CASE WHEN COALESCE(current_amount, 0) >= goal_amount THEN $2 ELSE $3 END,
+             closed_at = NOW(), updated_at = NOW()
+         WHERE id IN (
+             SELECT id FROM campaigns
+             WHERE status = $1 AND end_date IS NOT NULL AND end_date <= NOW()
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING id, title, slug, creator_id, status"
+    )
+    .bind(campaign_status::ACTIVE)
+    .bind(campaign_status::FUNDED)
+    .bind(campaign_status::ENDED)
+    .fetch_all(&mut tx)
+    .await?;
+
+    for (id, title, slug, creator_id, status) in &closed {
+        let (notification_kind, outcome) = if status == campaign_status::FUNDED {
+            (kind::CAMPAIGN_FUNDED, "reached its goal")
+        } else {
+            (kind::CAMPAIGN_ENDED, "ended without reaching its goal")
+        };
+        let notification = NewNotification {
+            kind: notification_kind,
+            title: format!("{} has {}", title, outcome),
+            message: None,
+            link: Some(format!("/campaigns/{}", slug)),
+            actor_id: None,
+        };
+
+        notifications::notify_campaign_backers(&mut tx, *id, &notification).await?;
+        notifications::notify_user(&mut tx, creator_id, &notification).await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(closed.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_collapses_punctuation_and_whitespace() {
+        assert_eq!(slugify("  Hello,   World!  "), "hello-world");
+        assert_eq!(slugify("--Save the Bees--"), "save-the-bees");
+        assert_eq!(slugify("Tom's Big Idea"), "toms-big-idea");
+    }
+
+    #[test]
+    fn slugify_transliterates_unicode() {
+        assert_eq!(slugify("Café Müller"), "cafe-muller");
+        assert_eq!(slugify("Çiğ Köfte Şöleni"), "cig-kofte-soleni");
+        assert_eq!(slugify("Привет мир"), "privet-mir");
+    }
+
+    #[test]
+    fn slugify_drops_input_without_alphanumerics() {
+        assert_eq!(slugify(""), "");
+        assert_eq!(slugify("!!! ??? ..."), "");
+    }
+
+    #[test]
+    fn slugify_truncates_without_trailing_separator() {
+        let slug = slugify(&"word ".repeat(40));
+        assert!(slug.len() <= MAX_SLUG_LENGTH);
+        assert!(!slug.ends_with('-'));
+
+        // The cut lands right after a separator
+        let title = format!("{} tail", "a".repeat(MAX_SLUG_LENGTH - 1));
+        assert_eq!(slugify(&title), "a".repeat(MAX_SLUG_LENGTH - 1));
+    }
+
+    #[test]
+    fn slugify_truncates_transliterated_text_to_the_limit() {
+        let slug = slugify(&"日本語".repeat(40));
+        assert!(slug.len() <= MAX_SLUG_LENGTH);
+        assert!(slug.is_ascii());
+    }
+
+    #[test]
+    fn is_valid_slug_rejects_empty_and_uuid_shaped_slugs() {
+        assert!(is_valid_slug("save-the-bees"));
+        assert!(!is_valid_slug(""));
+
+        let id = Uuid::new_v4();
+        assert!(!is_valid_slug(&id.to_string()));
+        assert!(!is_valid_slug(&id.simple().to_string()));
+        // slugify leaves a hyphenated UUID untouched, so it must be caught here
+        assert!(!is_valid_slug(&slugify(&id.to_string().to_uppercase())));
+    }
+
+    #[test]
+    fn update_request_tells_absent_fields_from_explicit_nulls() {
+        let payload: UpdateCampaignRequest =
+            serde_json::from_str(r#"{"story": null, "coverImage": "https://example.com/a.png"}"#).unwrap();
+
+        assert_eq!(payload.story, Some(None));
+        assert_eq!(payload.cover_image, Some(Some("https://example.com/a.png".to_string())));
+        assert_eq!(payload.video_url, None);
+        assert_eq!(payload.end_date, None);
+    }
+}
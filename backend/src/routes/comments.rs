@@ -0,0 +1,570 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post, put},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::post_status,
+    notifications::{self, kind, NewNotification},
+    routes::{
+        blocks::{hidden_users, is_blocked_between},
+        posts::fetch_readable_post,
+    },
+};
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    pub id: Uuid,
+    pub campaign_id: Option<Uuid>,
+    pub post_id: Option<Uuid>,
+    pub user_id: String,
+    pub username: Option<String>,
+    pub avatar: Option<String>,
+    pub parent_id: Option<Uuid>,
+    /// `None` once the comment has been deleted
+    pub content: Option<String>,
+    pub like_count: i32,
+    #[serde(rename = "pinned")]
+    pub is_pinned: bool,
+    #[serde(rename = "deleted")]
+    pub is_deleted: bool,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A top-level comment with its replies. Threads are one level deep: replies
+/// to a reply are attached to the same top-level comment.
+#[derive(Debug, Serialize)]
+pub struct CommentThread {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub replies: Vec<Comment>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCommentRequest {
+    pub content: String,
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EditCommentRequest {
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PinCommentRequest {
+    pub pinned: bool,
+}
+
+/// What a thread of comments hangs off
+#[derive(Debug, Clone, Copy)]
+enum CommentTarget {
+    Campaign(Uuid),
+    Post(Uuid),
+}
+
+impl CommentTarget {
+    fn column(self) -> &'static str {
+        match self {
+            CommentTarget::Campaign(_) => "campaign_id",
+            CommentTarget::Post(_) => "post_id",
+        }
+    }
+
+    fn id(self) -> Uuid {
+        match self {
+            CommentTarget::Campaign(id) | CommentTarget::Post(id) => id,
+        }
+    }
+}
+
+const MAX_COMMENT_LENGTH: usize = 5000;
+
+const COMMENT_SELECT: &str = "SELECT c.id, c.campaign_id, c.post_id, c.user_id, u.username, u.avatar_url AS avatar, c.parent_id,
+        CASE WHEN c.deleted_at IS NULL THEN c.content END AS content,
+        c.like_count, c.is_pinned, c.deleted_at IS NOT NULL AS is_deleted, c.edited_at, c.created_at, c.updated_at
+     FROM comments c
+     LEFT JOIN users u ON u.id = c.user_id";
+
+/// Routes mounted under `/api/comments`
+pub fn comment_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id", put(edit_comment).delete(delete_comment))
+        .route("/:id/pin", post(pin_comment))
+        .route("/:id/like", post(toggle_comment_like))
+}
+
+/// Routes merged into `/api/campaigns`
+pub fn campaign_comment_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id/comments", get(get_campaign_comments).post(create_campaign_comment))
+}
+
+/// Routes merged into `/api/posts`
+pub fn post_comment_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id/comments", get(get_post_comments).post(create_post_comment))
+}
+
+async fn get_campaign_comments(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+    Query(params): Query<CommentQuery>,
+    claims: Option<Claims>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    list_comments(&db, CommentTarget::Campaign(campaign_id), &params, claims.as_ref()).await
+}
+
+async fn get_post_comments(
+    State(db): State<Database>,
+    Path(post_id): Path<Uuid>,
+    Query(params): Query<CommentQuery>,
+    claims: Option<Claims>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    // Comments are as private as the post they are on
+    fetch_readable_post(&db, post_id, claims.as_ref().map(|c| c.sub.as_str())).await?;
+
+    list_comments(&db, CommentTarget::Post(post_id), &params, claims.as_ref()).await
+}
+
+async fn create_campaign_comment(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<CreateCommentRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let (slug, creator_id) = sqlx::query_as::<_, (String, String)>("SELECT slug, creator_id FROM campaigns WHERE id = $1")
+        .bind(campaign_id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let link = format!("/campaigns/{}", slug);
+    create_comment(&db, CommentTarget::Campaign(campaign_id), &creator_id, &link, &claims, &payload).await
+}
+
+async fn create_post_comment(
+    State(db): State<Database>,
+    Path(post_id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<CreateCommentRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    // Only readers of the post can comment, and unpublished posts not yet
+    let post = fetch_readable_post(&db, post_id, Some(&claims.sub)).await?;
+    if post.status != post_status::PUBLISHED {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    // The creator turned comments off for this post
+    if !post.comments_enabled {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let link = format!("/posts/{}", post_id);
+    create_comment(&db, CommentTarget::Post(post_id), &post.user_id, &link, &claims, &payload).await
+}
+
+async fn list_comments(
+    db: &Database,
+    target: CommentTarget,
+    params: &CommentQuery,
+    claims: Option<&Claims>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let viewer = claims.map(|c| c.sub.as_str());
+
+    // Deleted comments are only kept as placeholders while they still have replies
+    let visible = "(c.deleted_at IS NULL OR EXISTS(
+            SELECT 1 FROM comments r WHERE r.parent_id = c.id AND r.deleted_at IS NULL))";
+
+    let top_level = sqlx::query_as::<_, Comment>(&format!(
+        "{} WHERE c.{} = $1 AND c.parent_id IS NULL AND {} AND c.user_id NOT IN {}
+         ORDER BY c.is_pinned DESC, c.created_at DESC
+         LIMIT $2 OFFSET $3",
+        COMMENT_SELECT, target.column(), visible, hidden_users("$4")
+    ))
+    .bind(target.id())
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .bind(viewer)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching comments: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let total = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM comments c WHERE c.{} = $1 AND c.parent_id IS NULL AND {} AND c.user_id NOT IN {}",
+        target.column(), visible, hidden_users("$2")
+    ))
+    .bind(target.id())
+    .bind(viewer)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let threads = attach_replies(db, top_level, viewer).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": threads,
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": total,
+            "pages": ((total as f64) / (limit as f64)).ceil() as u32
+        }
+    })))
+}
+
+async fn create_comment(
+    db: &Database,
+    target: CommentTarget,
+    owner_id: &str,
+    link: &str,
+    claims: &Claims,
+    payload: &CreateCommentRequest,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let content = validate_content(&payload.content)?;
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let parent = match payload.parent_id {
+        Some(parent_id) => {
+            let parent = sqlx::query_as::<_, (Option<Uuid>, Option<Uuid>, String)>(&format!(
+                "SELECT {}, parent_id, user_id FROM comments WHERE id = $1 AND deleted_at IS NULL",
+                target.column()
+            ))
+            .bind(parent_id)
+            .fetch_optional(&mut tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .ok_or(StatusCode::NOT_FOUND)?;
+
+            if parent.0 != Some(target.id()) {
+                return Err(StatusCode::UNPROCESSABLE_ENTITY);
+            }
+
+            // Keep threads one level deep
+            Some((parent.1.unwrap_or(parent_id), parent.2))
+        }
+        None => None,
+    };
+
+    // Blocked either way from the creator or the comment being replied to
+    for other in std::iter::once(owner_id).chain(parent.as_ref().map(|(_, author)| author.as_str())) {
+        if is_blocked_between(&mut tx, &claims.sub, other)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        {
+            return Err(StatusCode::FORBIDDEN);
+        }
+    }
+
+    let comment_id = sqlx::query_scalar::<_, Uuid>(&format!(
+        "INSERT INTO comments ({}, user_id, parent_id, content) VALUES ($1, $2, $3, $4) RETURNING id",
+        target.column()
+    ))
+    .bind(target.id())
+    .bind(&claims.sub)
+    .bind(parent.as_ref().map(|(thread_id, _)| *thread_id))
+    .bind(content)
+    .fetch_one(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating comment: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if let CommentTarget::Post(post_id) = target {
+        adjust_post_comment_count(&mut tx, post_id, 1).await?;
+    }
+
+    if let Some((_, parent_author)) = parent.filter(|(_, author)| *author != claims.sub) {
+        notifications::notify_user(&mut tx, &parent_author, &NewNotification {
+            kind: kind::COMMENT_REPLY,
+            title: "Someone replied to your comment".to_string(),
+            message: Some(content.chars().take(140).collect()),
+            link: Some(format!("{}#comment-{}", link, comment_id)),
+            actor_id: Some(&claims.sub),
+        })
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let comment = fetch_comment(db, comment_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": comment
+    })))
+}
+
+async fn edit_comment(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<EditCommentRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let content = validate_content(&payload.content)?;
+
+    // Only the author can edit, and deleted comments stay deleted
+    let result = sqlx::query(
+        "UPDATE comments SET content = $3, edited_at = NOW(), updated_at = NOW()
+         WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL"
+    )
+    .bind(id)
+    .bind(&claims.sub)
+    .bind(content)
+    .execute(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        fetch_comment(&db, id).await?;
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let comment = fetch_comment(&db, id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": comment
+    })))
+}
+
+async fn delete_comment(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let (author_id, moderator_id) = comment_permissions(&db, id).await?;
+
+    // Authors remove their own comments; campaign and post creators moderate theirs
+    if author_id != claims.sub && moderator_id.as_deref() != Some(claims.sub.as_str()) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Soft delete so replies keep their thread
+    let deleted = sqlx::query_scalar::<_, Option<Uuid>>(
+        "UPDATE comments SET deleted_at = NOW(), is_pinned = false, updated_at = NOW()
+         WHERE id = $1 AND deleted_at IS NULL
+         RETURNING post_id"
+    )
+    .bind(id)
+    .fetch_optional(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if let Some(Some(post_id)) = deleted {
+        adjust_post_comment_count(&mut tx, post_id, -1).await?;
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Comment deleted"
+    })))
+}
+
+async fn pin_comment(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<PinCommentRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let (_, moderator_id) = comment_permissions(&db, id).await?;
+
+    if moderator_id.as_deref() != Some(claims.sub.as_str()) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    // Only live top-level comments can be pinned
+    let result = sqlx::query(
+        "UPDATE comments SET is_pinned = $2, updated_at = NOW()
+         WHERE id = $1 AND parent_id IS NULL AND deleted_at IS NULL"
+    )
+    .bind(id)
+    .bind(payload.pinned)
+    .execute(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let comment = fetch_comment(&db, id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": comment
+    })))
+}
+
+async fn toggle_comment_like(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Lock the comment so concurrent toggles keep the counter in step
+    let author_id = sqlx::query_scalar::<_, String>("SELECT user_id FROM comments WHERE id = $1 AND deleted_at IS NULL FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let unliked = sqlx::query("DELETE FROM comment_likes WHERE comment_id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(&claims.sub)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .rows_affected()
+        > 0;
+
+    if !unliked {
+        // Taking back a like still works after a block, giving a new one does not
+        if is_blocked_between(&mut tx, &claims.sub, &author_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        {
+            return Err(StatusCode::FORBIDDEN);
+        }
+
+        sqlx::query("INSERT INTO comment_likes (comment_id, user_id) VALUES ($1, $2)")
+            .bind(id)
+            .bind(&claims.sub)
+            .execute(&mut tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    let like_count = sqlx::query_scalar::<_, i32>(
+        "UPDATE comments SET like_count = GREATEST(like_count + $2, 0) WHERE id = $1 RETURNING like_count"
+    )
+    .bind(id)
+    .bind(if unliked { -1 } else { 1 })
+    .fetch_one(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "liked": !unliked,
+        "data": { "likeCount": like_count }
+    })))
+}
+
+/// The comment's author and the user who moderates the thread it is in
+async fn comment_permissions(db: &Database, id: Uuid) -> Result<(String, Option<String>), StatusCode> {
+    sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT c.user_id, COALESCE(ca.creator_id, p.user_id)
+         FROM comments c
+         LEFT JOIN campaigns ca ON ca.id = c.campaign_id
+         LEFT JOIN posts p ON p.id = c.post_id
+         WHERE c.id = $1"
+    )
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)
+}
+
+// `posts.comment_count` counts live comments, replies included
+async fn adjust_post_comment_count(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    post_id: Uuid,
+    delta: i32,
+) -> Result<(), StatusCode> {
+    sqlx::query("UPDATE posts SET comment_count = GREATEST(comment_count + $2, 0) WHERE id = $1")
+        .bind(post_id)
+        .bind(delta)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(())
+}
+
+async fn attach_replies(
+    db: &Database,
+    top_level: Vec<Comment>,
+    viewer: Option<&str>,
+) -> Result<Vec<CommentThread>, StatusCode> {
+    let thread_ids: Vec<Uuid> = top_level.iter().map(|comment| comment.id).collect();
+
+    let mut replies = sqlx::query_as::<_, Comment>(&format!(
+        "{} WHERE c.parent_id = ANY($1) AND c.deleted_at IS NULL AND c.user_id NOT IN {}
+         ORDER BY c.created_at",
+        COMMENT_SELECT, hidden_users("$2")
+    ))
+    .bind(&thread_ids)
+    .bind(viewer)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(top_level
+        .into_iter()
+        .map(|comment| {
+            let (thread_replies, rest) = replies
+                .drain(..)
+                .partition(|reply| reply.parent_id == Some(comment.id));
+            replies = rest;
+            CommentThread { comment, replies: thread_replies }
+        })
+        .collect())
+}
+
+fn validate_content(content: &str) -> Result<&str, StatusCode> {
+    let content = content.trim();
+    if content.is_empty() || content.chars().count() > MAX_COMMENT_LENGTH {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    Ok(content)
+}
+
+async fn fetch_comment(db: &Database, id: Uuid) -> Result<Comment, StatusCode> {
+    sqlx::query_as::<_, Comment>(&format!("{} WHERE c.id = $1", COMMENT_SELECT))
+        .bind(id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
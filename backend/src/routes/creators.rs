@@ -5,13 +5,28 @@ use axum::{
     routing::get,
     Router,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    auth::Claims,
     database::Database,
     models::User,
+    routes::{
+        follows::{follow_stats, FollowStats},
+        goals::creator_goal_routes,
+        tiers::creator_tier_routes,
+    },
 };
 
+/// A creator's public profile with follower counts
+#[derive(Debug, Serialize)]
+pub struct CreatorProfile {
+    #[serde(flatten)]
+    pub user: User,
+    #[serde(flatten)]
+    pub follows: FollowStats,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreatorQuery {
     pub limit: Option<i64>,
@@ -22,6 +37,8 @@ pub fn creator_routes() -> Router<Database> {
     Router::new()
         .route("/", get(get_creators))
         .route("/:username", get(get_creator_by_username))
+        .merge(creator_tier_routes())
+        .merge(creator_goal_routes())
 }
 
 async fn get_creators(
@@ -32,7 +49,8 @@ async fn get_creators(
     let offset = params.offset.unwrap_or(0);
     
     let query = r#"
-        SELECT id, email, name, username, avatar, bio, is_creator, created_at, updated_at 
+        SELECT id, COALESCE(email, '') AS email, COALESCE(display_name, username) AS name, username,
+               avatar_url AS avatar, bio, is_creator, created_at, updated_at
         FROM users 
         WHERE is_creator = true 
         ORDER BY created_at DESC 
@@ -56,9 +74,11 @@ async fn get_creators(
 async fn get_creator_by_username(
     State(db): State<Database>,
     Path(username): Path<String>,
-) -> Result<Json<User>, StatusCode> {
+    claims: Option<Claims>,
+) -> Result<Json<CreatorProfile>, StatusCode> {
     let query = r#"
-        SELECT id, email, name, username, avatar, bio, is_creator, created_at, updated_at 
+        SELECT id, COALESCE(email, '') AS email, COALESCE(display_name, username) AS name, username,
+               avatar_url AS avatar, bio, is_creator, created_at, updated_at
         FROM users 
         WHERE username = $1 AND is_creator = true
     "#;
@@ -68,7 +88,12 @@ async fn get_creator_by_username(
         .fetch_one(&db.pool)
         .await
     {
-        Ok(creator) => Ok(Json(creator)),
+        Ok(creator) => {
+            let follows = follow_stats(&db, &creator.id, claims.as_ref().map(|c| c.sub.as_str()))
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok(Json(CreatorProfile { user: creator, follows }))
+        }
         Err(sqlx::Error::RowNotFound) => Err(StatusCode::NOT_FOUND),
         Err(e) => {
             tracing::error!("Failed to fetch creator {}: {}", username, e);
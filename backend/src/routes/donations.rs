@@ -0,0 +1,559 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    config::Config,
+    database::Database,
+    models::{campaign_status, donation_status, funding_model},
+    payments::{to_minor_units, StripeClient},
+    routes::{
+        refunds::RefundTarget,
+        rewards::{claim_reward, create_fulfillment, release_reward, ShippingAddress},
+    },
+    validation::is_currency_code,
+};
+
+const MIN_DONATION_AMOUNT: f64 = 1.0;
+/// How long a donation may wait for its payment before its reward is released
+const PENDING_DONATION_TTL_MINUTES: i64 = 60;
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Donation {
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+    pub campaign_title: Option<String>,
+    pub campaign_slug: Option<String>,
+    pub user_id: Option<String>,
+    pub username: Option<String>,
+    pub avatar: Option<String>,
+    pub amount: f64,
+    pub currency: Option<String>,
+    pub message: Option<String>,
+    #[serde(rename = "anonymous")]
+    pub is_anonymous: bool,
+    pub status: String,
+    pub payment_method: Option<String>,
+    #[serde(rename = "transactionId")]
+    pub stripe_payment_intent_id: Option<String>,
+    pub refunded_amount: f64,
+    #[serde(rename = "rewardId")]
+    pub reward_tier_id: Option<Uuid>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Donation {
+    // Public listings must not reveal who gave anonymously
+    fn redacted(mut self) -> Self {
+        if self.is_anonymous {
+            self.user_id = None;
+            self.username = None;
+            self.avatar = None;
+        }
+        self.stripe_payment_intent_id = None;
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDonationRequest {
+    pub amount: f64,
+    pub message: Option<String>,
+    pub anonymous: Option<bool>,
+    pub payment_method: Option<String>,
+    pub currency: Option<String>,
+    pub reward_id: Option<Uuid>,
+    /// Required when the selected reward ships
+    pub shipping_address: Option<ShippingAddress>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DonationQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+const DONATION_SELECT: &str = "SELECT d.id, d.campaign_id, c.title AS campaign_title, c.slug AS campaign_slug,
+        d.user_id, u.username, u.avatar_url AS avatar, d.amount, d.currency, d.message, d.is_anonymous,
+        d.status, d.payment_method, d.stripe_payment_intent_id, d.refunded_amount, d.reward_tier_id, d.completed_at,
+        d.created_at, d.updated_at
+     FROM donations d
+     JOIN campaigns c ON c.id = d.campaign_id
+     LEFT JOIN users u ON u.id = d.user_id";
+
+/// Routes mounted under `/api/donations`
+pub fn donation_routes() -> Router<Database> {
+    Router::new()
+        .route("/me", get(get_my_donations))
+        .route("/:id", get(get_donation_by_id))
+}
+
+/// Routes merged into `/api/campaigns`
+pub fn campaign_donation_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id/donations", get(get_campaign_donations).post(create_donation))
+}
+
+/// Routes merged into `/api/users`
+pub fn user_donation_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id/donations", get(get_user_donations))
+}
+
+async fn create_donation(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<CreateDonationRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if !payload.amount.is_finite() || payload.amount < MIN_DONATION_AMOUNT {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let config = Config::from_env().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let stripe = StripeClient::from_config(&config).map_err(|e| {
+        eprintln!("Cannot accept donation: {}", e);
+        StatusCode::SERVICE_UNAVAILABLE
+    })?;
+
+    // Only live campaigns that have not passed their deadline take donations
+    let (is_open, campaign_funding_model, campaign_currency) = sqlx::query_as::<_, (bool, String, String)>(
+        "SELECT status = $2 AND (end_date IS NULL OR end_date > NOW()), funding_model, currency FROM campaigns WHERE id = $1"
+    )
+    .bind(campaign_id)
+    .bind(campaign_status::ACTIVE)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    if !is_open {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    // Totals and reward minimums are kept in the campaign's currency
+    let currency = match payload.currency.as_deref() {
+        Some(currency) if !is_currency_code(currency) => return Err(StatusCode::UNPROCESSABLE_ENTITY),
+        Some(currency) => currency.to_uppercase(),
+        None => campaign_currency.clone(),
+    };
+    if currency != campaign_currency {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if let Some(reward_id) = payload.reward_id {
+        let requires_shipping = claim_reward(&mut tx, campaign_id, reward_id, payload.amount).await?;
+        if requires_shipping && payload.shipping_address.is_none() {
+            return Err(StatusCode::UNPROCESSABLE_ENTITY);
+        }
+    }
+
+    // The donation stays PENDING until Stripe confirms the payment via webhook
+    let donation_id = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO donations (campaign_id, user_id, amount, currency, message, is_anonymous, status, payment_method, reward_tier_id)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+         RETURNING id"
+    )
+    .bind(campaign_id)
+    .bind(&claims.sub)
+    .bind(payload.amount)
+    .bind(&currency)
+    .bind(&payload.message)
+    .bind(payload.anonymous.unwrap_or(false))
+    .bind(donation_status::PENDING)
+    .bind(&payload.payment_method)
+    .bind(payload.reward_id)
+    .fetch_one(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating donation: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if let Some(reward_id) = payload.reward_id {
+        create_fulfillment(&mut tx, donation_id, reward_id, payload.shipping_address.as_ref()).await?;
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let metadata = [
+        ("donation_id", donation_id.to_string()),
+        ("campaign_id", campaign_id.to_string()),
+    ];
+    // All-or-nothing pledges are only authorized now and captured when the campaign closes
+    let manual_capture = campaign_funding_model == funding_model::ALL_OR_NOTHING;
+    let intent = match stripe
+        .create_payment_intent(to_minor_units(payload.amount, &currency), &currency, manual_capture, &metadata)
+        .await
+    {
+        Ok(intent) => intent,
+        Err(e) => {
+            eprintln!("Payment intent for donation {} failed: {}", donation_id, e);
+            let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            sqlx::query("UPDATE donations SET status = $2, updated_at = NOW() WHERE id = $1")
+                .bind(donation_id)
+                .bind(donation_status::FAILED)
+                .execute(&mut tx)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            release_reward(&mut tx, donation_id)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            return Err(StatusCode::BAD_GATEWAY);
+        }
+    };
+
+    sqlx::query("UPDATE donations SET stripe_payment_intent_id = $2, updated_at = NOW() WHERE id = $1")
+        .bind(donation_id)
+        .bind(&intent.id)
+        .execute(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let donation = fetch_donation(&db, donation_id).await?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "data": donation,
+        "clientSecret": intent.client_secret,
+        "paymentStatus": intent.status
+    });
+
+    Ok(Json(response))
+}
+
+async fn get_campaign_donations(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+    Query(params): Query<DonationQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let donations = sqlx::query_as::<_, Donation>(&format!(
+        "{} WHERE d.campaign_id = $1 AND d.status = ANY($2) ORDER BY d.created_at DESC LIMIT $3 OFFSET $4",
+        DONATION_SELECT
+    ))
+    .bind(campaign_id)
+    .bind(donation_status::BACKED)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching campaign donations: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let (total, backers) = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT COUNT(*), COUNT(DISTINCT user_id) FROM donations WHERE campaign_id = $1 AND status = ANY($2)"
+    )
+    .bind(campaign_id)
+    .bind(donation_status::BACKED)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let donations: Vec<Donation> = donations.into_iter().map(Donation::redacted).collect();
+
+    let response = serde_json::json!({
+        "success": true,
+        "data": donations,
+        "backers": backers,
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": total,
+            "pages": ((total as f64) / (limit as f64)).ceil() as u32
+        }
+    });
+
+    Ok(Json(response))
+}
+
+async fn get_my_donations(
+    State(db): State<Database>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let donations = sqlx::query_as::<_, Donation>(&format!(
+        "{} WHERE d.user_id = $1 ORDER BY d.created_at DESC",
+        DONATION_SELECT
+    ))
+    .bind(&claims.sub)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching donations: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "data": donations
+    });
+
+    Ok(Json(response))
+}
+
+async fn get_user_donations(
+    State(db): State<Database>,
+    Path(user_id): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    // Anonymous gifts are left out of a donor's public history
+    let donations = sqlx::query_as::<_, Donation>(&format!(
+        "{} WHERE d.user_id = $1 AND d.is_anonymous = false AND d.status = ANY($2) ORDER BY d.created_at DESC",
+        DONATION_SELECT
+    ))
+    .bind(&user_id)
+    .bind(donation_status::BACKED)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let donations: Vec<Donation> = donations.into_iter().map(Donation::redacted).collect();
+
+    let response = serde_json::json!({
+        "success": true,
+        "data": donations
+    });
+
+    Ok(Json(response))
+}
+
+async fn get_donation_by_id(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let donation = fetch_donation(&db, id).await?;
+
+    // Visible to the donor and the campaign creator
+    let creator_id = sqlx::query_scalar::<_, String>("SELECT creator_id FROM campaigns WHERE id = $1")
+        .bind(donation.campaign_id)
+        .fetch_one(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let is_donor = donation.user_id.as_deref() == Some(claims.sub.as_str());
+    if !is_donor && creator_id != claims.sub {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let donation = if is_donor { donation } else { donation.redacted() };
+
+    let response = serde_json::json!({
+        "success": true,
+        "data": donation
+    });
+
+    Ok(Json(response))
+}
+
+/// Whether the user currently backs the campaign (including uncaptured pledges)
+pub(crate) async fn is_campaign_backer(db: &Database, campaign_id: Uuid, user_id: &str) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM donations WHERE campaign_id = $1 AND user_id = $2 AND status = ANY($3))"
+    )
+    .bind(campaign_id)
+    .bind(user_id)
+    .bind(donation_status::BACKED)
+    .fetch_one(&db.pool)
+    .await
+}
+
+async fn fetch_donation(db: &Database, donation_id: Uuid) -> Result<Donation, StatusCode> {
+    sqlx::query_as::<_, Donation>(&format!("{} WHERE d.id = $1", DONATION_SELECT))
+        .bind(donation_id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Settle authorized all-or-nothing pledges on campaigns that are no longer
+/// active: capture them if the campaign was funded, release them otherwise.
+/// Each pledge is settled in its own transaction and its outcome written to
+/// the payment audit log. Returns how many pledges were processed.
+pub(crate) async fn settle_closed_pledges(db: &Database) -> Result<usize, sqlx::Error> {
+    let Ok(config) = Config::from_env() else {
+        return Ok(0);
+    };
+    let Ok(stripe) = StripeClient::from_config(&config) else {
+        return Ok(0);
+    };
+
+    let mut processed = 0;
+    loop {
+        let mut tx = db.pool.begin().await?;
+
+        // The row lock is held across the Stripe call so no other runner settles the same pledge
+        let pledge = sqlx::query_as::<_, (Uuid, Uuid, f64, Option<String>, String)>(
+            "SELECT d.id, d.campaign_id, d.amount, d.stripe_payment_intent_id, c.status
+             FROM donations d
+             JOIN campaigns c ON c.id = d.campaign_id
+             WHERE d.status = $1 AND c.status <> $2
+             ORDER BY d.created_at
+             LIMIT 1
+             FOR UPDATE OF d SKIP LOCKED"
+        )
+        .bind(donation_status::AUTHORIZED)
+        .bind(campaign_status::ACTIVE)
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let Some((donation_id, campaign_id, amount, payment_intent_id, campaign_state)) = pledge else {
+            break;
+        };
+
+        let capture = campaign_state == campaign_status::FUNDED;
+        let result = match payment_intent_id.as_deref() {
+            Some(id) if capture => stripe.capture_payment_intent(id).await.map(|_| ()),
+            Some(id) => stripe.cancel_payment_intent(id).await.map(|_| ()),
+            None => Ok(()),
+        };
+
+        let (to_status, action, error) = match (&result, capture) {
+            (Ok(()), true) => (donation_status::COMPLETED, "pledge_captured", None),
+            (Ok(()), false) => (donation_status::RELEASED, "pledge_released", None),
+            (Err(e), true) => (donation_status::FAILED, "pledge_capture_failed", Some(e.to_string())),
+            (Err(e), false) => (donation_status::RELEASED, "pledge_release_failed", Some(e.to_string())),
+        };
+
+        sqlx::query(
+            "UPDATE donations
+             SET status = $2, settled_at = NOW(), settlement_error = $3,
+                 completed_at = CASE WHEN $2 = $4 THEN NOW() ELSE completed_at END, updated_at = NOW()
+             WHERE id = $1"
+        )
+        .bind(donation_id)
+        .bind(to_status)
+        .bind(&error)
+        .bind(donation_status::COMPLETED)
+        .execute(&mut tx)
+        .await?;
+
+        // Money that was never collected no longer counts towards the campaign
+        if to_status != donation_status::COMPLETED {
+            sqlx::query(
+                "UPDATE campaigns SET current_amount = GREATEST(COALESCE(current_amount, 0) - $2, 0), updated_at = NOW()
+                 WHERE id = $1"
+            )
+            .bind(campaign_id)
+            .bind(amount)
+            .execute(&mut tx)
+            .await?;
+
+            release_reward(&mut tx, donation_id).await?;
+        }
+
+        sqlx::query(
+            "INSERT INTO payment_audit_log (target_type, target_id, action, from_status, to_status, amount, details)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)"
+        )
+        .bind(RefundTarget::Donation.as_str())
+        .bind(donation_id)
+        .bind(action)
+        .bind(donation_status::AUTHORIZED)
+        .bind(to_status)
+        .bind(amount)
+        .bind(error.or(payment_intent_id))
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+        processed += 1;
+    }
+
+    Ok(processed)
+}
+
+/// Expire donations whose payment was never confirmed, which Stripe sends no
+/// webhook for: cancel the intent, mark the donation `FAILED` and put its
+/// reward back on offer. Intents Stripe refuses to cancel, e.g. because the
+/// payment is already processing, are left for their webhook. Returns how many
+/// donations were expired.
+pub(crate) async fn expire_stale_donations(db: &Database) -> Result<usize, sqlx::Error> {
+    let Ok(config) = Config::from_env() else {
+        return Ok(0);
+    };
+    let Ok(stripe) = StripeClient::from_config(&config) else {
+        return Ok(0);
+    };
+
+    let cutoff = Utc::now() - chrono::Duration::minutes(PENDING_DONATION_TTL_MINUTES);
+    let mut skipped: Vec<Uuid> = Vec::new();
+    let mut expired = 0;
+    loop {
+        let mut tx = db.pool.begin().await?;
+
+        // The row lock is held across the Stripe call so a late webhook waits for the outcome
+        let donation = sqlx::query_as::<_, (Uuid, f64, Option<String>)>(
+            "SELECT id, amount, stripe_payment_intent_id
+             FROM donations
+             WHERE status = $1 AND created_at < $2 AND id <> ALL($3)
+             ORDER BY created_at
+             LIMIT 1
+             FOR UPDATE SKIP LOCKED"
+        )
+        .bind(donation_status::PENDING)
+        .bind(cutoff)
+        .bind(&skipped)
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let Some((donation_id, amount, payment_intent_id)) = donation else {
+            break;
+        };
+
+        if let Some(id) = payment_intent_id.as_deref() {
+            if let Err(e) = stripe.cancel_payment_intent(id).await {
+                tracing::warn!("Could not cancel payment intent {} of donation {}: {}", id, donation_id, e);
+                skipped.push(donation_id);
+                continue;
+            }
+        }
+
+        sqlx::query("UPDATE donations SET status = $2, updated_at = NOW() WHERE id = $1")
+            .bind(donation_id)
+            .bind(donation_status::FAILED)
+            .execute(&mut tx)
+            .await?;
+
+        release_reward(&mut tx, donation_id).await?;
+
+        sqlx::query(
+            "INSERT INTO payment_audit_log (target_type, target_id, action, from_status, to_status, amount, details)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)"
+        )
+        .bind(RefundTarget::Donation.as_str())
+        .bind(donation_id)
+        .bind("donation_expired")
+        .bind(donation_status::PENDING)
+        .bind(donation_status::FAILED)
+        .bind(amount)
+        .bind(payment_intent_id)
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+        expired += 1;
+    }
+
+    Ok(expired)
+}
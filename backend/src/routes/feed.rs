@@ -0,0 +1,316 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::{content_type, donation_status, post_status, purchase_status, subscription_status},
+    routes::{
+        blocks::hidden_users,
+        posts::{fetch_readable_post, POST_SELECT},
+    },
+};
+
+const DEFAULT_FEED_LIMIT: i64 = 20;
+const MAX_FEED_LIMIT: i64 = 50;
+const EXCERPT_LENGTH: i32 = 280;
+
+/// One entry in the subscriber feed, whatever kind of content it is
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedItem {
+    pub content_type: String,
+    pub id: String,
+    pub creator_id: String,
+    pub creator_name: String,
+    pub creator_username: Option<String>,
+    pub creator_avatar: Option<String>,
+    pub title: String,
+    pub excerpt: Option<String>,
+    pub published_at: DateTime<Utc>,
+    /// The viewer cannot open the full item (premium post or backers-only update)
+    pub locked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeedQuery {
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Position of the last item on a page: `<published_at micros>.<type>.<id>`
+struct FeedCursor {
+    published_at: DateTime<Utc>,
+    content_type: String,
+    id: String,
+}
+
+impl FeedCursor {
+    fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.splitn(3, '.');
+        let micros = parts.next()?.parse::<i64>().ok()?;
+        let content_type = parts.next()?.to_string();
+        let id = parts.next()?.to_string();
+
+        Some(FeedCursor {
+            published_at: DateTime::from_timestamp_micros(micros)?,
+            content_type,
+            id,
+        })
+    }
+
+    fn encode(item: &FeedItem) -> String {
+        format!("{}.{}.{}", item.published_at.timestamp_micros(), item.content_type, item.id)
+    }
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Bookmark {
+    pub id: Uuid,
+    pub user_id: String,
+    pub content_type: String,
+    pub content_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookmarkRequest {
+    pub content_type: String,
+    pub content_id: String,
+}
+
+pub fn feed_routes() -> Router<Database> {
+    Router::new()
+        .route("/", get(get_feed))
+        .route("/bookmarks", get(get_bookmarks).post(add_bookmark).delete(remove_bookmark))
+}
+
+// Everything published by creators the viewer subscribes to or follows, newest
+// first, leaving out creators the viewer blocked or muted or who blocked them.
+// Items are ordered by (published_at, type, id) so the cursor is stable even
+// when several items share a timestamp.
+async fn get_feed(
+    State(db): State<Database>,
+    Query(params): Query<FeedQuery>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let limit = params.limit.unwrap_or(DEFAULT_FEED_LIMIT).clamp(1, MAX_FEED_LIMIT);
+    let cursor = match params.cursor.as_deref() {
+        Some(value) => Some(FeedCursor::parse(value).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+
+    // Articles, podcasts and events only show up once their tables exist
+    let mut optional_items = String::new();
+    for (table, branch) in [
+        (
+            "articles",
+            format!(
+                "SELECT '{}', a.id::text, a.author_id, a.title, LEFT(a.content, {excerpt}), a.published_at, FALSE
+                 FROM articles a
+                 WHERE a.published_at <= NOW() AND a.author_id IN (SELECT creator_id FROM sources)",
+                content_type::ARTICLE,
+                excerpt = EXCERPT_LENGTH,
+            ),
+        ),
+        (
+            "podcasts",
+            format!(
+                "SELECT '{}', pc.id::text, pc.creator_id, pc.title, LEFT(pc.description, {excerpt}), pc.published_at, FALSE
+                 FROM podcasts pc
+                 WHERE pc.published_at <= NOW() AND pc.creator_id IN (SELECT creator_id FROM sources)",
+                content_type::PODCAST,
+                excerpt = EXCERPT_LENGTH,
+            ),
+        ),
+        (
+            "events",
+            format!(
+                "SELECT '{}', e.id::text, e.host_id, e.title, LEFT(e.description, {excerpt}), e.created_at, FALSE
+                 FROM events e
+                 WHERE e.host_id IN (SELECT creator_id FROM sources)",
+                content_type::EVENT,
+                excerpt = EXCERPT_LENGTH,
+            ),
+        ),
+    ] {
+        if db.table_exists(table).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? {
+            optional_items.push_str("UNION ALL\n");
+            optional_items.push_str(&branch);
+            optional_items.push('\n');
+        }
+    }
+
+    let mut items = sqlx::query_as::<_, FeedItem>(&format!(
+        r#"
+        WITH sources AS (
+            SELECT creator_id FROM subscriptions
+            WHERE user_id = $1 AND status = ANY($2)
+              AND (current_period_end IS NULL OR current_period_end > NOW())
+            UNION
+            SELECT followee_id FROM follows WHERE follower_id = $1
+            EXCEPT {hidden}
+        ),
+        items AS (
+            SELECT '{post}' AS content_type, p.id::text AS id, p.user_id AS creator_id, p.title,
+                   CASE WHEN p.locked THEN COALESCE(p.preview, LEFT(p.content, {excerpt})) ELSE LEFT(p.content, {excerpt}) END AS excerpt,
+                   p.published_at, p.locked
+            FROM ({post_select}) p
+            WHERE p.status = $4 AND p.user_id IN (SELECT creator_id FROM sources)
+            {optional_items}
+            UNION ALL
+            SELECT '{campaign_update}', u.id::text, u.author_id, u.title,
+                   CASE WHEN l.locked THEN NULL ELSE LEFT(u.content, {excerpt}) END, u.created_at, l.locked
+            FROM campaign_updates u,
+                 LATERAL (SELECT u.backers_only AND NOT EXISTS(
+                     SELECT 1 FROM donations d
+                     WHERE d.campaign_id = u.campaign_id AND d.user_id = $1 AND d.status = ANY($5)
+                 ) AS locked) l
+            WHERE u.author_id IN (SELECT creator_id FROM sources)
+        )
+        SELECT i.content_type, i.id, i.creator_id,
+               COALESCE(us.display_name, us.username) AS creator_name, us.username AS creator_username,
+               us.avatar_url AS creator_avatar, i.title, i.excerpt, i.published_at, i.locked
+        FROM items i
+        JOIN users us ON us.id = i.creator_id
+        WHERE i.published_at IS NOT NULL
+          AND ($6::timestamptz IS NULL OR (i.published_at, i.content_type, i.id) < ($6, $7, $8))
+        ORDER BY i.published_at DESC, i.content_type DESC, i.id DESC
+        LIMIT $9
+        "#,
+        post = content_type::POST,
+        optional_items = optional_items,
+        campaign_update = content_type::CAMPAIGN_UPDATE,
+        excerpt = EXCERPT_LENGTH,
+        post_select = POST_SELECT,
+        hidden = hidden_users("$1"),
+    ))
+    .bind(&claims.sub)
+    .bind(subscription_status::ENTITLED)
+    .bind(purchase_status::SETTLED)
+    .bind(post_status::PUBLISHED)
+    .bind(donation_status::BACKED)
+    .bind(cursor.as_ref().map(|c| c.published_at))
+    .bind(cursor.as_ref().map(|c| c.content_type.as_str()))
+    .bind(cursor.as_ref().map(|c| c.id.as_str()))
+    .bind(limit + 1)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching feed: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    // The extra row only tells us whether another page exists
+    let has_more = items.len() as i64 > limit;
+    items.truncate(limit as usize);
+    let next_cursor = if has_more { items.last().map(FeedCursor::encode) } else { None };
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": items,
+        "nextCursor": next_cursor
+    })))
+}
+
+async fn get_bookmarks(
+    State(db): State<Database>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let bookmarks = sqlx::query_as::<_, Bookmark>(
+        "SELECT id, user_id, content_type, content_id, created_at FROM bookmarks
+         WHERE user_id = $1 ORDER BY created_at DESC"
+    )
+    .bind(&claims.sub)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching bookmarks: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": bookmarks
+    })))
+}
+
+async fn add_bookmark(
+    State(db): State<Database>,
+    claims: Claims,
+    Json(payload): Json<BookmarkRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let table = match payload.content_type.as_str() {
+        content_type::POST => "posts",
+        content_type::ARTICLE => "articles",
+        content_type::EVENT => "events",
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+
+    if payload.content_type == content_type::POST {
+        // Only posts the user can open can be saved, so drafts and gated posts stay hidden
+        let post_id = Uuid::parse_str(&payload.content_id).map_err(|_| StatusCode::NOT_FOUND)?;
+        fetch_readable_post(&db, post_id, Some(&claims.sub)).await?;
+    } else {
+        // Ids are compared as text since events use string ids
+        let exists = sqlx::query_scalar::<_, bool>(&format!(
+            "SELECT EXISTS(SELECT 1 FROM {} WHERE id::text = $1)",
+            table
+        ))
+        .bind(&payload.content_id)
+        .fetch_one(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        if !exists {
+            return Err(StatusCode::NOT_FOUND);
+        }
+    }
+
+    // Saving something twice is a no-op
+    sqlx::query(
+        "INSERT INTO bookmarks (user_id, content_type, content_id) VALUES ($1, $2, $3)
+         ON CONFLICT (user_id, content_type, content_id) DO NOTHING"
+    )
+    .bind(&claims.sub)
+    .bind(&payload.content_type)
+    .bind(&payload.content_id)
+    .execute(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error adding bookmark: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true
+    })))
+}
+
+async fn remove_bookmark(
+    State(db): State<Database>,
+    claims: Claims,
+    Json(payload): Json<BookmarkRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    sqlx::query("DELETE FROM bookmarks WHERE user_id = $1 AND content_type = $2 AND content_id = $3")
+        .bind(&claims.sub)
+        .bind(&payload.content_type)
+        .bind(&payload.content_id)
+        .execute(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true
+    })))
+}
@@ -0,0 +1,268 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    notifications::{self, kind, NewNotification},
+    routes::blocks::{hidden_users, is_blocked_between},
+};
+
+/// A user in a followers or following list, with how they relate to the viewer.
+/// The follow is mutual when both `is_following` and `follows_you` are set.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowUser {
+    pub id: String,
+    pub username: String,
+    pub name: Option<String>,
+    pub avatar: Option<String>,
+    pub bio: Option<String>,
+    pub is_creator: bool,
+    pub followed_at: DateTime<Utc>,
+    /// The viewer follows this user
+    pub is_following: bool,
+    /// This user follows the viewer
+    pub follows_you: bool,
+    pub is_mutual: bool,
+}
+
+/// Follower counts for a profile, and the viewer's follows either way
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowStats {
+    pub follower_count: i64,
+    pub following_count: i64,
+    pub is_following: bool,
+    pub follows_you: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FollowQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+/// Users on the other side of a follow, seen by $2 (the viewer, may be null)
+const FOLLOW_USER_SELECT: &str = "SELECT u.id, u.username, u.display_name AS name, u.avatar_url AS avatar, u.bio,
+            COALESCE(u.is_creator, FALSE) AS is_creator, f.created_at AS followed_at,
+            EXISTS(SELECT 1 FROM follows v WHERE v.follower_id = $2 AND v.followee_id = u.id) AS is_following,
+            EXISTS(SELECT 1 FROM follows v WHERE v.follower_id = u.id AND v.followee_id = $2) AS follows_you,
+            EXISTS(SELECT 1 FROM follows v WHERE v.follower_id = $2 AND v.followee_id = u.id)
+                AND EXISTS(SELECT 1 FROM follows v WHERE v.follower_id = u.id AND v.followee_id = $2) AS is_mutual
+     FROM follows f";
+
+/// Routes merged into `/api/users`
+pub fn user_follow_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id/follow", post(follow_user).delete(unfollow_user))
+        .route("/:id/followers", get(get_followers))
+        .route("/:id/following", get(get_following))
+}
+
+// Following twice is a no-op; only the first follow notifies
+async fn follow_user(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if id == claims.sub {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let followee_is_creator = sqlx::query_scalar::<_, Option<bool>>("SELECT is_creator FROM users WHERE id = $1")
+        .bind(&id)
+        .fetch_optional(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?
+        .unwrap_or(false);
+
+    if is_blocked_between(&mut tx, &claims.sub, &id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let inserted = sqlx::query(
+        "INSERT INTO follows (follower_id, followee_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+    )
+    .bind(&claims.sub)
+    .bind(&id)
+    .execute(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error following user: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .rows_affected()
+        > 0;
+
+    if inserted && followee_is_creator {
+        notify_new_follower(&mut tx, &id, &claims.sub)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let stats = follow_stats(&db, &id, Some(&claims.sub))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": stats
+    })))
+}
+
+async fn unfollow_user(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    sqlx::query("DELETE FROM follows WHERE follower_id = $1 AND followee_id = $2")
+        .bind(&claims.sub)
+        .bind(&id)
+        .execute(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let stats = follow_stats(&db, &id, Some(&claims.sub))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": stats
+    })))
+}
+
+async fn get_followers(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    Query(params): Query<FollowQuery>,
+    claims: Option<Claims>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    list_follows(&db, &id, claims.as_ref(), params, "followers").await
+}
+
+async fn get_following(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    Query(params): Query<FollowQuery>,
+    claims: Option<Claims>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    list_follows(&db, &id, claims.as_ref(), params, "following").await
+}
+
+// Most recent follows first. Users hidden from the viewer are left out of the
+// page but still counted in the total.
+async fn list_follows(
+    db: &Database,
+    user_id: &str,
+    claims: Option<&Claims>,
+    params: FollowQuery,
+    direction: &'static str,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    // Followers are on the follower side of follows into the user, and the
+    // other way round for who the user follows
+    let (user_column, owner_column) = if direction == "followers" {
+        ("follower_id", "followee_id")
+    } else {
+        ("followee_id", "follower_id")
+    };
+
+    let users = sqlx::query_as::<_, FollowUser>(&format!(
+        "{select} JOIN users u ON u.id = f.{user}
+         WHERE f.{owner} = $1 AND u.id NOT IN {hidden}
+         ORDER BY f.created_at DESC
+         LIMIT $3 OFFSET $4",
+        select = FOLLOW_USER_SELECT,
+        user = user_column,
+        owner = owner_column,
+        hidden = hidden_users("$2"),
+    ))
+    .bind(user_id)
+    .bind(claims.map(|c| c.sub.as_str()))
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching {}: {:?}", direction, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM follows WHERE {} = $1", owner_column))
+        .bind(user_id)
+        .fetch_one(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            direction: users,
+            "pagination": {
+                "page": page,
+                "limit": limit,
+                "total": total,
+                "pages": ((total as f64) / (limit as f64)).ceil() as u32
+            }
+        }
+    })))
+}
+
+pub(crate) async fn follow_stats(
+    db: &Database,
+    user_id: &str,
+    viewer_id: Option<&str>,
+) -> Result<FollowStats, sqlx::Error> {
+    sqlx::query_as::<_, FollowStats>(
+        "SELECT (SELECT COUNT(*) FROM follows WHERE followee_id = $1) AS follower_count,
+                (SELECT COUNT(*) FROM follows WHERE follower_id = $1) AS following_count,
+                EXISTS(SELECT 1 FROM follows WHERE follower_id = $2 AND followee_id = $1) AS is_following,
+                EXISTS(SELECT 1 FROM follows WHERE follower_id = $1 AND followee_id = $2) AS follows_you"
+    )
+    .bind(user_id)
+    .bind(viewer_id)
+    .fetch_one(&db.pool)
+    .await
+}
+
+async fn notify_new_follower(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    creator_id: &str,
+    follower_id: &str,
+) -> Result<(), sqlx::Error> {
+    let follower = sqlx::query_scalar::<_, String>(
+        "SELECT COALESCE(display_name, username) FROM users WHERE id = $1"
+    )
+    .bind(follower_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    notifications::notify_user(tx, creator_id, &NewNotification {
+        kind: kind::NEW_FOLLOWER,
+        title: format!("{} started following you", follower),
+        message: None,
+        link: None,
+        actor_id: Some(follower_id),
+    })
+    .await
+}
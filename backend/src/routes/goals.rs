@@ -0,0 +1,435 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::{goal_kind, purchase_status, subscription_status},
+    notifications::{self, kind, NewNotification},
+    routes::stream::{event_kind, record_stream_event, NewStreamEvent},
+};
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatorGoal {
+    pub id: Uuid,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub target_amount: f64,
+    pub currency: String,
+    pub current_amount: f64,
+    /// Percentage of the target, capped at 100
+    pub progress: f64,
+    pub remaining: f64,
+    pub reward_description: Option<String>,
+    pub deadline: Option<DateTime<Utc>>,
+    pub is_public: bool,
+    pub is_completed: bool,
+    /// The deadline passed; the goal is no longer marked as reached
+    pub is_expired: bool,
+    pub reached_at: Option<DateTime<Utc>>,
+    #[sqlx(flatten)]
+    pub creator: GoalCreator,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct GoalCreator {
+    #[sqlx(rename = "creator_id")]
+    pub id: String,
+    #[sqlx(rename = "creator_name")]
+    pub name: String,
+    #[sqlx(rename = "creator_avatar")]
+    pub avatar: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatorGoalRequest {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub target_amount: f64,
+    pub currency: Option<String>,
+    pub reward_description: Option<String>,
+    pub deadline: Option<DateTime<Utc>>,
+    pub is_public: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalQuery {
+    pub creator_id: Option<String>,
+}
+
+/// Goals with their progress. $1 is the entitled subscription statuses and $2
+/// the settled purchase statuses. Amounts are net of refunds and only count
+/// charges in the goal's currency.
+const GOAL_SELECT: &str = "SELECT g.id, g.kind, g.title, g.description, g.target_amount, g.currency,
+            cur.amount AS current_amount,
+            LEAST(cur.amount / g.target_amount * 100, 100) AS progress,
+            GREATEST(g.target_amount - cur.amount, 0) AS remaining,
+            g.reward_description, g.deadline, g.is_public,
+            cur.amount >= g.target_amount AS is_completed,
+            COALESCE(g.deadline <= NOW(), FALSE) AS is_expired, g.reached_at,
+            g.creator_id, COALESCE(u.display_name, u.username) AS creator_name, u.avatar_url AS creator_avatar,
+            g.created_at, g.updated_at
+     FROM creator_goals g
+     JOIN users u ON u.id = g.creator_id
+     CROSS JOIN LATERAL (
+         SELECT CASE g.kind
+             WHEN 'SUBSCRIBERS' THEN
+                 (SELECT COUNT(DISTINCT s.user_id) FROM subscriptions s
+                  WHERE s.creator_id = g.creator_id AND s.status = ANY($1)
+                    AND (s.current_period_end IS NULL OR s.current_period_end > NOW()))::float8
+             WHEN 'REVENUE' THEN
+                 (SELECT COALESCE(SUM(COALESCE(s.last_charge_amount, 0) - s.refunded_amount), 0) FROM subscriptions s
+                  WHERE s.creator_id = g.creator_id
+                    AND s.last_charged_at >= date_trunc('month', NOW())
+                    AND UPPER(s.last_charge_currency) = g.currency)
+                 + (SELECT COALESCE(SUM(pu.amount - pu.refunded_amount), 0)::float8 FROM purchases pu
+                    JOIN products pr ON pr.id = pu.product_id
+                    WHERE pr.user_id = g.creator_id AND pu.status = ANY($2)
+                      AND UPPER(pu.currency) = g.currency
+                      AND pu.created_at >= date_trunc('month', NOW()))
+             ELSE
+                 (SELECT COALESCE(SUM(pu.amount - pu.refunded_amount), 0)::float8 FROM purchases pu
+                  JOIN products pr ON pr.id = pu.product_id
+                  WHERE pr.user_id = g.creator_id AND pu.status = ANY($2)
+                    AND UPPER(pu.currency) = g.currency
+                    AND pu.created_at >= g.created_at)
+         END AS amount
+     ) cur";
+
+/// Routes mounted under `/api/goals`
+pub fn goal_routes() -> Router<Database> {
+    Router::new()
+        .route("/", get(get_my_goals).post(create_goal))
+        .route("/creator/:creator_id", get(get_goals_by_creator))
+        .route("/:id", get(get_goal).put(update_goal).delete(delete_goal))
+}
+
+/// Routes merged into `/api/creators`
+pub fn creator_goal_routes() -> Router<Database> {
+    Router::new()
+        .route("/:username/goals", get(get_creator_goals))
+}
+
+async fn get_my_goals(
+    State(db): State<Database>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let goals = sqlx::query_as::<_, CreatorGoal>(&format!(
+        "{} WHERE g.creator_id = $3 ORDER BY g.created_at",
+        GOAL_SELECT
+    ))
+    .bind(subscription_status::ENTITLED)
+    .bind(purchase_status::SETTLED)
+    .bind(&claims.sub)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(goal_error)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": goals
+    })))
+}
+
+// Private goals are only listed for their creator
+async fn get_goals_by_creator(
+    State(db): State<Database>,
+    Path(creator_id): Path<String>,
+    claims: Option<Claims>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let goals = sqlx::query_as::<_, CreatorGoal>(&format!(
+        "{} WHERE g.creator_id = $3 AND (g.is_public OR g.creator_id = $4) ORDER BY g.created_at",
+        GOAL_SELECT
+    ))
+    .bind(subscription_status::ENTITLED)
+    .bind(purchase_status::SETTLED)
+    .bind(&creator_id)
+    .bind(claims.as_ref().map(|c| c.sub.as_str()))
+    .fetch_all(&db.pool)
+    .await
+    .map_err(goal_error)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": goals
+    })))
+}
+
+// Shown on the creator's public profile
+async fn get_creator_goals(
+    State(db): State<Database>,
+    Path(username): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let goals = sqlx::query_as::<_, CreatorGoal>(&format!(
+        "{} WHERE g.creator_id = (SELECT id FROM users WHERE username = $3) AND g.is_public ORDER BY g.created_at",
+        GOAL_SELECT
+    ))
+    .bind(subscription_status::ENTITLED)
+    .bind(purchase_status::SETTLED)
+    .bind(&username)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(goal_error)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": goals
+    })))
+}
+
+// Polled by the stream goal widget without a token. `default` with a
+// `creatorId` picks the creator's oldest public goal still in progress and
+// not past its deadline.
+async fn get_goal(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    Query(params): Query<GoalQuery>,
+    claims: Option<Claims>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let viewer = claims.as_ref().map(|c| c.sub.as_str());
+
+    let goal = if id == "default" {
+        let creator_id = params.creator_id.ok_or(StatusCode::BAD_REQUEST)?;
+        sqlx::query_as::<_, CreatorGoal>(&format!(
+            "{} WHERE g.creator_id = $3 AND g.is_public AND (g.deadline IS NULL OR g.deadline > NOW())
+             ORDER BY cur.amount >= g.target_amount, g.created_at
+             LIMIT 1",
+            GOAL_SELECT
+        ))
+        .bind(subscription_status::ENTITLED)
+        .bind(purchase_status::SETTLED)
+        .bind(creator_id)
+        .fetch_optional(&db.pool)
+        .await
+    } else {
+        let id = Uuid::parse_str(&id).map_err(|_| StatusCode::NOT_FOUND)?;
+        sqlx::query_as::<_, CreatorGoal>(&format!(
+            "{} WHERE g.id = $3 AND (g.is_public OR g.creator_id = $4)",
+            GOAL_SELECT
+        ))
+        .bind(subscription_status::ENTITLED)
+        .bind(purchase_status::SETTLED)
+        .bind(id)
+        .bind(viewer)
+        .fetch_optional(&db.pool)
+        .await
+    }
+    .map_err(goal_error)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": goal
+    })))
+}
+
+async fn create_goal(
+    State(db): State<Database>,
+    claims: Claims,
+    Json(payload): Json<CreatorGoalRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if !db.is_creator(&claims.sub).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let currency = validate_goal(&payload)?;
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO creator_goals
+             (creator_id, kind, title, description, target_amount, currency, reward_description, deadline, is_public)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+         RETURNING id"
+    )
+    .bind(&claims.sub)
+    .bind(&payload.kind)
+    .bind(payload.title.trim())
+    .bind(&payload.description)
+    .bind(payload.target_amount)
+    .bind(&currency)
+    .bind(&payload.reward_description)
+    .bind(payload.deadline)
+    .bind(payload.is_public.unwrap_or(true))
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating goal: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let goal = fetch_goal(&db, id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": goal
+    })))
+}
+
+// Changing what a goal measures lets it be reached, and announced, again
+async fn update_goal(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<CreatorGoalRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let currency = validate_goal(&payload)?;
+
+    let updated = sqlx::query_scalar::<_, Uuid>(
+        "UPDATE creator_goals
+         SET reached_at = CASE WHEN kind = $3 AND target_amount = $6 AND currency = $7 THEN reached_at END,
+             kind = $3, title = $4, description = $5, target_amount = $6, currency = $7,
+             reward_description = $8, deadline = $9, is_public = COALESCE($10, is_public), updated_at = NOW()
+         WHERE id = $1 AND creator_id = $2
+         RETURNING id"
+    )
+    .bind(id)
+    .bind(&claims.sub)
+    .bind(&payload.kind)
+    .bind(payload.title.trim())
+    .bind(&payload.description)
+    .bind(payload.target_amount)
+    .bind(&currency)
+    .bind(&payload.reward_description)
+    .bind(payload.deadline)
+    .bind(payload.is_public)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error updating goal: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if updated.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let goal = fetch_goal(&db, id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": goal
+    })))
+}
+
+async fn delete_goal(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let result = sqlx::query("DELETE FROM creator_goals WHERE id = $1 AND creator_id = $2")
+        .bind(id)
+        .bind(&claims.sub)
+        .execute(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Goal deleted"
+    })))
+}
+
+/// Mark goals whose progress has caught up with their target as reached,
+/// firing a stream event and notifying the creator once per goal. Revenue
+/// goals are monthly, so they can be reached again each month. Only goals
+/// that can still be reached are computed; expired goals never are.
+pub(crate) async fn mark_reached_goals(db: &Database) -> Result<usize, sqlx::Error> {
+    let mut tx = db.pool.begin().await?;
+
+    let reached = sqlx::query_as::<_, (String, String, f64)>(&format!(
+        "UPDATE creator_goals g
+         SET reached_at = NOW()
+         FROM ({} WHERE (g.reached_at IS NULL OR (g.kind = $3 AND g.reached_at < date_trunc('month', NOW())))
+                 AND (g.deadline IS NULL OR g.deadline > NOW())) p
+         WHERE g.id = p.id AND p.is_completed
+         RETURNING g.creator_id, g.title, g.target_amount",
+        GOAL_SELECT
+    ))
+    .bind(subscription_status::ENTITLED)
+    .bind(purchase_status::SETTLED)
+    .bind(goal_kind::REVENUE)
+    .fetch_all(&mut tx)
+    .await?;
+
+    for (creator_id, title, target_amount) in &reached {
+        record_stream_event(&mut tx, NewStreamEvent {
+            creator_id,
+            kind: event_kind::GOAL_REACHED,
+            title: format!("Goal reached: {}", title),
+            message: None,
+            amount: Some(*target_amount),
+            campaign_id: None,
+        })
+        .await?;
+
+        notifications::notify_user(&mut tx, creator_id, &NewNotification {
+            kind: kind::GOAL_REACHED,
+            title: format!("You reached your goal: {}", title),
+            message: None,
+            link: Some("/creator-dashboard/goals".to_string()),
+            actor_id: None,
+        })
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(reached.len())
+}
+
+/// Check a goal request and return its normalized currency
+fn validate_goal(payload: &CreatorGoalRequest) -> Result<String, StatusCode> {
+    let currency = payload.currency.as_deref().unwrap_or("USD").trim().to_uppercase();
+
+    if !goal_kind::is_valid(&payload.kind)
+        || payload.title.trim().is_empty()
+        || !payload.target_amount.is_finite()
+        || payload.target_amount <= 0.0
+        || currency.len() != 3
+        || payload.deadline.is_some_and(|deadline| deadline <= Utc::now())
+    {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    // Subscriber counts are whole numbers
+    if payload.kind == goal_kind::SUBSCRIBERS && payload.target_amount.fract() != 0.0 {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    Ok(currency)
+}
+
+async fn fetch_goal(db: &Database, id: Uuid) -> Result<CreatorGoal, StatusCode> {
+    sqlx::query_as::<_, CreatorGoal>(&format!("{} WHERE g.id = $3", GOAL_SELECT))
+        .bind(subscription_status::ENTITLED)
+        .bind(purchase_status::SETTLED)
+        .bind(id)
+        .fetch_one(&db.pool)
+        .await
+        .map_err(goal_error)
+}
+
+fn goal_error(e: sqlx::Error) -> StatusCode {
+    eprintln!("Error fetching goals: {:?}", e);
+    StatusCode::INTERNAL_SERVER_ERROR
+}
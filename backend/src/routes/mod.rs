@@ -1,9 +1,30 @@
 pub mod auth;
 pub mod articles;
+pub mod blocks;
+pub mod campaign_updates;
 pub mod campaigns;
+pub mod comments;
 pub mod creators;
+pub mod donations;
 pub mod events;
+pub mod feed;
+pub mod follows;
+pub mod goals;
+pub mod notifications;
 pub mod podcasts;
+pub mod polls;
+pub mod post_attachments;
+pub mod post_revisions;
 pub mod posts;
 pub mod products;
+pub mod refunds;
+pub mod reports;
+pub mod reviews;
+pub mod rewards;
+pub mod search;
+pub mod stream;
+pub mod stretch_goals;
+pub mod tags;
+pub mod tiers;
 pub mod users;
+pub mod webhooks;
@@ -0,0 +1,141 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{auth::Claims, database::Database};
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub id: Uuid,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub title: String,
+    pub message: Option<String>,
+    pub link: Option<String>,
+    pub actor_id: Option<String>,
+    pub is_read: bool,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationQuery {
+    /// `createdAt` of the last item from the previous page
+    pub cursor: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+    pub unread_only: Option<bool>,
+}
+
+pub fn notification_routes() -> Router<Database> {
+    Router::new()
+        .route("/", get(get_notifications))
+        .route("/mark-all-read", post(mark_all_read))
+        .route("/:id/read", post(mark_as_read))
+}
+
+async fn get_notifications(
+    State(db): State<Database>,
+    claims: Option<Claims>,
+    Query(params): Query<NotificationQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    // Signed-out visitors simply have no notifications
+    let Some(claims) = claims else {
+        return Ok(Json(serde_json::json!({
+            "success": true,
+            "data": { "items": [], "unreadCount": 0, "nextCursor": null }
+        })));
+    };
+
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+
+    let items = sqlx::query_as::<_, Notification>(
+        "SELECT id, kind, title, message, link, actor_id, read_at IS NOT NULL AS is_read, read_at, created_at
+         FROM notifications
+         WHERE user_id = $1
+           AND ($2::timestamptz IS NULL OR created_at < $2)
+           AND (NOT $3 OR read_at IS NULL)
+         ORDER BY created_at DESC
+         LIMIT $4"
+    )
+    .bind(&claims.sub)
+    .bind(params.cursor)
+    .bind(params.unread_only.unwrap_or(false))
+    .bind(limit as i64)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching notifications: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let unread_count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND read_at IS NULL"
+    )
+    .bind(&claims.sub)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let next_cursor = if items.len() == limit as usize {
+        items.last().map(|item| item.created_at)
+    } else {
+        None
+    };
+
+    let response = serde_json::json!({
+        "success": true,
+        "data": {
+            "items": items,
+            "unreadCount": unread_count,
+            "nextCursor": next_cursor
+        }
+    });
+
+    Ok(Json(response))
+}
+
+async fn mark_as_read(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let result = sqlx::query(
+        "UPDATE notifications SET read_at = COALESCE(read_at, NOW()) WHERE id = $1 AND user_id = $2"
+    )
+    .bind(id)
+    .bind(&claims.sub)
+    .execute(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+async fn mark_all_read(
+    State(db): State<Database>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let result = sqlx::query("UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND read_at IS NULL")
+        .bind(&claims.sub)
+        .execute(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": { "updated": result.rows_affected() }
+    })))
+}
@@ -0,0 +1,410 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::{post_status, purchase_status, subscription_status, Post},
+    routes::{blocks::is_blocked_between, posts::POST_SELECT},
+};
+
+const MIN_OPTIONS: usize = 2;
+const MAX_OPTIONS: usize = 10;
+const MAX_QUESTION_LENGTH: usize = 300;
+const MAX_LABEL_LENGTH: usize = 200;
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Poll {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub question: String,
+    pub multiple_choice: bool,
+    pub closes_at: Option<DateTime<Utc>>,
+    pub subscribers_only: bool,
+    pub hide_results_until_close: bool,
+    /// Withheld while results are hidden
+    pub total_votes: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct PollOption {
+    pub id: Uuid,
+    pub position: i32,
+    pub label: String,
+    /// Withheld while results are hidden
+    pub vote_count: Option<i32>,
+}
+
+/// A poll as shown to one viewer, also embedded in the post response
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollView {
+    pub poll: Poll,
+    pub options: Vec<PollOption>,
+    pub closed: bool,
+    pub results_hidden: bool,
+    pub my_votes: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePollRequest {
+    pub question: String,
+    pub options: Vec<String>,
+    pub multiple_choice: Option<bool>,
+    pub closes_at: Option<DateTime<Utc>>,
+    pub subscribers_only: Option<bool>,
+    pub hide_results_until_close: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoteRequest {
+    pub option_ids: Vec<Uuid>,
+}
+
+const POLL_SELECT: &str = "SELECT id, post_id, question, multiple_choice, closes_at, subscribers_only,
+            hide_results_until_close, total_votes, created_at
+     FROM polls";
+
+/// Routes merged into `/api/posts`
+pub fn post_poll_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id/poll", get(get_poll).post(create_poll))
+        .route("/:id/poll/vote", post(vote))
+        .route("/:id/poll/close", post(close_poll))
+}
+
+impl Poll {
+    fn is_closed(&self) -> bool {
+        self.closes_at.is_some_and(|at| at <= Utc::now())
+    }
+}
+
+async fn create_poll(
+    State(db): State<Database>,
+    Path(post_id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<CreatePollRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    validate_poll(&payload)?;
+
+    let post = fetch_post(&db, post_id, Some(&claims.sub)).await?;
+    if post.user_id != claims.sub {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // One poll per post
+    let poll_id = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO polls (post_id, question, multiple_choice, closes_at, subscribers_only, hide_results_until_close)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (post_id) DO NOTHING
+         RETURNING id"
+    )
+    .bind(post_id)
+    .bind(payload.question.trim())
+    .bind(payload.multiple_choice.unwrap_or(false))
+    .bind(payload.closes_at)
+    .bind(payload.subscribers_only.unwrap_or(false))
+    .bind(payload.hide_results_until_close.unwrap_or(false))
+    .fetch_optional(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating poll: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::CONFLICT)?;
+
+    for (position, label) in payload.options.iter().enumerate() {
+        sqlx::query("INSERT INTO poll_options (poll_id, position, label) VALUES ($1, $2, $3)")
+            .bind(poll_id)
+            .bind(position as i32)
+            .bind(label.trim())
+            .execute(&mut tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    poll_response(&db, &post, Some(&claims.sub)).await
+}
+
+async fn get_poll(
+    State(db): State<Database>,
+    Path(post_id): Path<Uuid>,
+    claims: Option<Claims>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let viewer = claims.as_ref().map(|c| c.sub.as_str());
+    let post = fetch_post(&db, post_id, viewer).await?;
+
+    // Polls on unpublished posts are only visible to the author, like the post
+    if post.status != post_status::PUBLISHED && viewer != Some(post.user_id.as_str()) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    // and polls on gated posts only to viewers who can read it
+    if post.locked {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    poll_response(&db, &post, viewer).await
+}
+
+async fn vote(
+    State(db): State<Database>,
+    Path(post_id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<VoteRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let post = fetch_post(&db, post_id, Some(&claims.sub)).await?;
+    if post.status != post_status::PUBLISHED {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    if post.locked {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Locking the poll serializes votes with closing it, so none land after the close
+    let poll = sqlx::query_as::<_, Poll>(&format!("{} WHERE post_id = $1 FOR UPDATE", POLL_SELECT))
+        .bind(post_id)
+        .fetch_optional(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if poll.is_closed() {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let choices: HashSet<Uuid> = payload.option_ids.iter().copied().collect();
+    if choices.is_empty() || (!poll.multiple_choice && choices.len() > 1) {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    if poll.subscribers_only && post.user_id != claims.sub && !is_subscriber(&db, &claims.sub, &post.user_id).await? {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    if is_blocked_between(&mut tx, &claims.sub, &post.user_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    // The ballot's primary key is what stops a second vote, even from concurrent requests
+    let cast = sqlx::query("INSERT INTO poll_ballots (poll_id, user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+        .bind(poll.id)
+        .bind(&claims.sub)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .rows_affected()
+        > 0;
+
+    if !cast {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let choices: Vec<Uuid> = choices.into_iter().collect();
+    let counted = sqlx::query(
+        "UPDATE poll_options SET vote_count = vote_count + 1 WHERE poll_id = $1 AND id = ANY($2)"
+    )
+    .bind(poll.id)
+    .bind(&choices)
+    .execute(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .rows_affected();
+
+    // Every choice must be an option of this poll
+    if counted != choices.len() as u64 {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    sqlx::query(
+        "INSERT INTO poll_votes (poll_id, user_id, option_id) SELECT $1, $2, UNNEST($3::uuid[])"
+    )
+    .bind(poll.id)
+    .bind(&claims.sub)
+    .bind(&choices)
+    .execute(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error recording poll vote: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    sqlx::query("UPDATE polls SET total_votes = total_votes + 1 WHERE id = $1")
+        .bind(poll.id)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    poll_response(&db, &post, Some(&claims.sub)).await
+}
+
+// Closing early reveals hidden results
+async fn close_poll(
+    State(db): State<Database>,
+    Path(post_id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let post = fetch_post(&db, post_id, Some(&claims.sub)).await?;
+    if post.user_id != claims.sub {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let closed = sqlx::query(
+        "UPDATE polls SET closes_at = NOW() WHERE post_id = $1 AND (closes_at IS NULL OR closes_at > NOW())"
+    )
+    .bind(post_id)
+    .execute(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .rows_affected()
+        > 0;
+
+    if !closed {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    poll_response(&db, &post, Some(&claims.sub)).await
+}
+
+async fn poll_response(db: &Database, post: &Post, viewer: Option<&str>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let poll = fetch_poll(db, post.id).await?;
+    let view = poll_view(db, poll, post, viewer).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": view
+    })))
+}
+
+/// The poll on `post` for embedding in the post response; `None` when the
+/// post has no poll or the viewer cannot read the post
+pub(crate) async fn post_poll(db: &Database, post: &Post, viewer: Option<&str>) -> Result<Option<PollView>, StatusCode> {
+    if post.locked {
+        return Ok(None);
+    }
+
+    match find_poll(db, post.id).await? {
+        Some(poll) => Ok(Some(poll_view(db, poll, post, viewer).await?)),
+        None => Ok(None),
+    }
+}
+
+/// The poll with its options and the viewer's own choices. Counts are left
+/// out while results are hidden, except for the post's author.
+async fn poll_view(db: &Database, mut poll: Poll, post: &Post, viewer: Option<&str>) -> Result<PollView, StatusCode> {
+    let mut options = sqlx::query_as::<_, PollOption>(
+        "SELECT id, position, label, vote_count FROM poll_options WHERE poll_id = $1 ORDER BY position"
+    )
+    .bind(poll.id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let my_votes = sqlx::query_scalar::<_, Uuid>(
+        "SELECT option_id FROM poll_votes WHERE poll_id = $1 AND user_id = $2"
+    )
+    .bind(poll.id)
+    .bind(viewer)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let results_hidden = poll.hide_results_until_close && !poll.is_closed() && viewer != Some(post.user_id.as_str());
+    if results_hidden {
+        poll.total_votes = None;
+        for option in options.iter_mut() {
+            option.vote_count = None;
+        }
+    }
+
+    Ok(PollView {
+        closed: poll.is_closed(),
+        poll,
+        options,
+        results_hidden,
+        my_votes,
+    })
+}
+
+async fn find_poll(db: &Database, post_id: Uuid) -> Result<Option<Poll>, StatusCode> {
+    sqlx::query_as::<_, Poll>(&format!("{} WHERE post_id = $1", POLL_SELECT))
+        .bind(post_id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+async fn fetch_poll(db: &Database, post_id: Uuid) -> Result<Poll, StatusCode> {
+    find_poll(db, post_id).await?.ok_or(StatusCode::NOT_FOUND)
+}
+
+// The post as `viewer` sees it, including whether it is locked for them
+async fn fetch_post(db: &Database, post_id: Uuid, viewer: Option<&str>) -> Result<Post, StatusCode> {
+    sqlx::query_as::<_, Post>(&format!("{} WHERE p.id = $4", POST_SELECT))
+        .bind(viewer)
+        .bind(subscription_status::ENTITLED)
+        .bind(purchase_status::SETTLED)
+        .bind(post_id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn is_subscriber(db: &Database, user_id: &str, creator_id: &str) -> Result<bool, StatusCode> {
+    sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(
+            SELECT 1 FROM subscriptions
+            WHERE user_id = $1 AND creator_id = $2 AND status = ANY($3)
+              AND (current_period_end IS NULL OR current_period_end > NOW()))"
+    )
+    .bind(user_id)
+    .bind(creator_id)
+    .bind(subscription_status::ENTITLED)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+fn validate_poll(payload: &CreatePollRequest) -> Result<(), StatusCode> {
+    let question = payload.question.trim();
+    let labels_valid = payload.options.iter().all(|label| {
+        let label = label.trim();
+        !label.is_empty() && label.chars().count() <= MAX_LABEL_LENGTH
+    });
+
+    if question.is_empty()
+        || question.chars().count() > MAX_QUESTION_LENGTH
+        || !(MIN_OPTIONS..=MAX_OPTIONS).contains(&payload.options.len())
+        || !labels_valid
+        || payload.closes_at.is_some_and(|at| at <= Utc::now())
+    {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    Ok(())
+}
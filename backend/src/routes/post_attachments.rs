@@ -0,0 +1,122 @@
+use axum::http::StatusCode;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    database::Database,
+    models::{AttachmentRequest, Post, PostAttachment, PostWithAttachments},
+    validation::is_http_url,
+};
+
+const MAX_ATTACHMENTS: usize = 20;
+
+/// Attach each post's attachments, withholding the URLs the viewer has no
+/// access to. Attachments follow the post's gating unless marked free or
+/// premium; premium ones on a free post are for the author and subscribers.
+pub(crate) async fn with_attachments(db: &Database, posts: Vec<Post>) -> Result<Vec<PostWithAttachments>, StatusCode> {
+    let ids: Vec<Uuid> = posts.iter().map(|p| p.id).collect();
+
+    let rows = sqlx::query_as::<_, PostAttachment>(
+        "SELECT id, post_id, position, url, mime_type, width, height, duration_seconds, alt_text, is_premium, created_at
+         FROM post_attachments WHERE post_id = ANY($1) ORDER BY position"
+    )
+    .bind(&ids)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching post attachments: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut by_post: HashMap<Uuid, Vec<PostAttachment>> = HashMap::new();
+    for attachment in rows {
+        by_post.entry(attachment.post_id).or_default().push(attachment);
+    }
+
+    Ok(posts
+        .into_iter()
+        .map(|post| {
+            let mut attachments = by_post.remove(&post.id).unwrap_or_default();
+            for attachment in attachments.iter_mut() {
+                let withheld = match attachment.is_premium {
+                    Some(false) => false,
+                    Some(true) if !post.is_gated() => post.members_locked,
+                    _ => post.locked,
+                };
+                if withheld {
+                    attachment.url = None;
+                }
+            }
+            PostWithAttachments { post, attachments }
+        })
+        .collect())
+}
+
+pub(crate) async fn with_attachments_one(db: &Database, post: Post) -> Result<PostWithAttachments, StatusCode> {
+    with_attachments(db, vec![post])
+        .await?
+        .pop()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+pub(crate) fn validate_attachments(attachments: &[AttachmentRequest]) -> Result<(), StatusCode> {
+    if attachments.len() > MAX_ATTACHMENTS {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    for attachment in attachments {
+        let valid = is_http_url(&attachment.url)
+            && is_mime_type(&attachment.mime_type)
+            && attachment.width.is_none_or(|w| w > 0)
+            && attachment.height.is_none_or(|h| h > 0)
+            && attachment.duration_seconds.is_none_or(|d| d.is_finite() && d >= 0.0);
+        if !valid {
+            return Err(StatusCode::UNPROCESSABLE_ENTITY);
+        }
+    }
+
+    Ok(())
+}
+
+/// Swap the post's attachments for `attachments`, keeping their order
+pub(crate) async fn replace_attachments(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    post_id: Uuid,
+    attachments: &[AttachmentRequest],
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM post_attachments WHERE post_id = $1")
+        .bind(post_id)
+        .execute(&mut *tx)
+        .await?;
+
+    for (position, attachment) in attachments.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO post_attachments (post_id, position, url, mime_type, width, height, duration_seconds, alt_text, is_premium)
+             VALUES ($1, $2, $3, LOWER($4), $5, $6, $7, $8, $9)"
+        )
+        .bind(post_id)
+        .bind(position as i32)
+        .bind(&attachment.url)
+        .bind(attachment.mime_type.trim())
+        .bind(attachment.width)
+        .bind(attachment.height)
+        .bind(attachment.duration_seconds)
+        .bind(&attachment.alt_text)
+        .bind(attachment.is_premium)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+// `type/subtype`, e.g. `image/png` or `application/pdf`
+fn is_mime_type(value: &str) -> bool {
+    match value.trim().split_once('/') {
+        Some((kind, subtype)) => {
+            let token = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || "!#$&^_.+-".contains(c));
+            token(kind) && token(subtype)
+        }
+        None => false,
+    }
+}
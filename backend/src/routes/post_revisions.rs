@@ -0,0 +1,244 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::{Post, PostWithAttachments},
+    routes::{post_attachments::with_attachments_one, posts::lock_own_post},
+};
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct PostRevision {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub revision: i32,
+    pub title: String,
+    pub content: Option<String>,
+    pub media_url: Option<String>,
+    pub media_type: Option<String>,
+    pub is_premium: bool,
+    pub editor_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+/// A run of consecutive content lines that were kept, added or removed
+#[derive(Debug, Serialize)]
+struct DiffChunk {
+    op: &'static str,
+    text: String,
+}
+
+const REVISION_SELECT: &str = "SELECT id, post_id, revision, title, content, media_url, media_type, is_premium, editor_id, created_at
+     FROM post_revisions";
+
+/// Routes merged into `/api/posts`
+pub fn post_revision_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id/revisions", get(list_revisions))
+        .route("/:id/revisions/diff", get(diff_revisions))
+        .route("/:id/revisions/:revision/restore", post(restore_revision))
+}
+
+/// Snapshot the post as it is now as its next revision. The caller must hold
+/// the post's row lock so revision numbers stay sequential.
+pub(crate) async fn record_revision(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    post_id: Uuid,
+    editor_id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO post_revisions (post_id, revision, title, content, media_url, media_type, is_premium, editor_id)
+         SELECT id, COALESCE((SELECT MAX(revision) FROM post_revisions WHERE post_id = $1), 0) + 1,
+                title, content, media_url, media_type, is_premium, $2
+         FROM posts WHERE id = $1"
+    )
+    .bind(post_id)
+    .bind(editor_id)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Posts written before revisions were kept get their current state stored
+/// as revision 1 before the first edit, so it can still be restored.
+pub(crate) async fn ensure_first_revision(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    post_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO post_revisions (post_id, revision, title, content, media_url, media_type, is_premium, editor_id, created_at)
+         SELECT id, 1, title, content, media_url, media_type, is_premium, user_id, updated_at
+         FROM posts
+         WHERE id = $1 AND NOT EXISTS(SELECT 1 FROM post_revisions WHERE post_id = $1)"
+    )
+    .bind(post_id)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+// Revisions can hold premium content, so only the author sees them
+async fn list_revisions(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_author(&db, id, &claims.sub).await?;
+
+    let revisions = sqlx::query_as::<_, PostRevision>(&format!(
+        "{} WHERE post_id = $1 ORDER BY revision DESC",
+        REVISION_SELECT
+    ))
+    .bind(id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching post revisions: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": revisions
+    })))
+}
+
+async fn diff_revisions(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<DiffQuery>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_author(&db, id, &claims.sub).await?;
+
+    let from = fetch_revision(&db, id, params.from).await?;
+    let to = fetch_revision(&db, id, params.to).await?;
+
+    let old_content = from.content.as_deref().unwrap_or("");
+    let new_content = to.content.as_deref().unwrap_or("");
+
+    let mut content: Vec<DiffChunk> = Vec::new();
+    for change in TextDiff::from_lines(old_content, new_content).iter_all_changes() {
+        let op = match change.tag() {
+            ChangeTag::Equal => "equal",
+            ChangeTag::Insert => "insert",
+            ChangeTag::Delete => "delete",
+        };
+        match content.last_mut() {
+            Some(chunk) if chunk.op == op => chunk.text.push_str(change.value()),
+            _ => content.push(DiffChunk { op, text: change.value().to_string() }),
+        }
+    }
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            "from": from.revision,
+            "to": to.revision,
+            "title": field_change(&from.title, &to.title),
+            "mediaUrl": field_change(&from.media_url, &to.media_url),
+            "mediaType": field_change(&from.media_type, &to.media_type),
+            "isPremium": field_change(&from.is_premium, &to.is_premium),
+            "content": content
+        }
+    })))
+}
+
+// Restoring is itself an edit, so it is recorded as a new revision
+async fn restore_revision(
+    State(db): State<Database>,
+    Path((id, revision)): Path<(Uuid, i32)>,
+    claims: Claims,
+) -> Result<Json<PostWithAttachments>, StatusCode> {
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    lock_own_post(&mut tx, id, &claims.sub).await?;
+
+    ensure_first_revision(&mut tx, id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // A post still gated by a tier or product stays premium
+    let post = sqlx::query_as::<_, Post>(
+        "UPDATE posts p
+         SET title = r.title, content = r.content, media_url = r.media_url, media_type = r.media_type,
+             is_premium = r.is_premium OR p.min_tier_id IS NOT NULL OR p.product_id IS NOT NULL,
+             updated_at = NOW()
+         FROM post_revisions r
+         WHERE p.id = $1 AND r.post_id = p.id AND r.revision = $2
+         RETURNING p.*"
+    )
+    .bind(id)
+    .bind(revision)
+    .fetch_optional(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error restoring post revision: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    record_revision(&mut tx, id, &claims.sub)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(with_attachments_one(&db, post).await?))
+}
+
+async fn ensure_author(db: &Database, post_id: Uuid, user_id: &str) -> Result<(), StatusCode> {
+    let author_id = sqlx::query_scalar::<_, String>("SELECT user_id FROM posts WHERE id = $1")
+        .bind(post_id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if author_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(())
+}
+
+async fn fetch_revision(db: &Database, post_id: Uuid, revision: i32) -> Result<PostRevision, StatusCode> {
+    sqlx::query_as::<_, PostRevision>(&format!(
+        "{} WHERE post_id = $1 AND revision = $2",
+        REVISION_SELECT
+    ))
+    .bind(post_id)
+    .bind(revision)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)
+}
+
+// `null` when the field is the same in both revisions
+fn field_change<T: Serialize + PartialEq>(from: &T, to: &T) -> serde_json::Value {
+    if from == to {
+        serde_json::Value::Null
+    } else {
+        serde_json::json!({ "from": from, "to": to })
+    }
+}
@@ -5,20 +5,77 @@ use axum::{
     routing::{get, post, put, delete},
     Router,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
     auth::Claims,
     database::Database,
-    models::{CreatePostRequest, Post},
+    models::{content_type, post_status, purchase_status, subscription_status, CreatePostRequest, Post, PostWithAttachments},
+    notifications::{self, kind, NewNotification},
+    routes::{
+        blocks::{blocked_users, hidden_users, is_blocked_between},
+        comments::post_comment_routes,
+        polls::{post_poll, post_poll_routes, PollView},
+        post_attachments::{replace_attachments, validate_attachments, with_attachments, with_attachments_one},
+        post_revisions::{ensure_first_revision, post_revision_routes, record_revision},
+        tags::{set_content_tags, tag_filter},
+    },
 };
 
+// Posts as seen by viewer `$1`. A gated post is readable by its author, by
+// active subscribers at or above `min_tier_id` (any tier when unset), and by
+// buyers of `product_id`. A post gated only by a product is not unlocked by
+// subscribing. `members_locked` is the same check for any active subscription,
+// which premium attachments on free posts need.
+pub(crate) const POST_SELECT: &str = "SELECT p.*, t.name AS required_tier_name, t.price AS required_tier_price,
+        COALESCE(NOT (
+            p.user_id = $1
+            OR (NOT p.is_premium AND p.min_tier_id IS NULL AND p.product_id IS NULL)
+            OR (p.is_premium AND (p.product_id IS NULL OR p.min_tier_id IS NOT NULL) AND EXISTS(
+                SELECT 1 FROM subscriptions s
+                LEFT JOIN membership_tiers st ON st.id = s.tier_id
+                WHERE s.user_id = $1 AND s.creator_id = p.user_id AND s.status = ANY($2)
+                  AND (s.current_period_end IS NULL OR s.current_period_end > NOW())
+                  AND (t.id IS NULL OR st.price >= t.price)))
+            OR (p.product_id IS NOT NULL AND EXISTS(
+                SELECT 1 FROM purchases pu
+                WHERE pu.user_id = $1 AND pu.product_id = p.product_id AND pu.status = ANY($3)))
+        ), true) AS locked,
+        COALESCE(NOT (
+            p.user_id = $1
+            OR EXISTS(
+                SELECT 1 FROM subscriptions s
+                WHERE s.user_id = $1 AND s.creator_id = p.user_id AND s.status = ANY($2)
+                  AND (s.current_period_end IS NULL OR s.current_period_end > NOW()))
+        ), true) AS members_locked
+     FROM posts p
+     LEFT JOIN membership_tiers t ON t.id = p.min_tier_id";
+
+const TEASER_LENGTH: usize = 280;
+
 #[derive(Debug, Deserialize)]
 pub struct PostQuery {
     pub page: Option<u32>,
     pub limit: Option<u32>,
-    pub user_id: Option<Uuid>,
+    pub user_id: Option<String>,
+    /// Only posts carrying a tag with this slug
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePostRequest {
+    pub publish_at: DateTime<Utc>,
+}
+
+/// A single post with its poll and the poll's current results, if it has one
+#[derive(Debug, Serialize)]
+pub struct PostDetail {
+    #[serde(flatten)]
+    post: PostWithAttachments,
+    poll: Option<PollView>,
 }
 
 pub fn post_routes() -> Router<Database> {
@@ -29,12 +86,18 @@ pub fn post_routes() -> Router<Database> {
         .route("/:id", get(get_post_by_id))
         .route("/:id", put(update_post))
         .route("/:id", delete(delete_post))
+        .route("/:id/publish", post(publish_post))
+        .route("/:id/schedule", put(schedule_post).delete(unschedule_post))
+        .route("/:id/like", post(toggle_post_like))
+        .merge(post_comment_routes())
+        .merge(post_revision_routes())
+        .merge(post_poll_routes())
 }
 
 #[derive(Debug, Serialize)]
 struct PostsResponse {
     success: bool,
-    data: Vec<Post>,
+    data: Vec<PostWithAttachments>,
     pagination: PaginationInfo,
 }
 
@@ -49,33 +112,47 @@ struct PaginationInfo {
 async fn get_posts(
     State(db): State<Database>,
     Query(params): Query<PostQuery>,
+    claims: Option<Claims>,
 ) -> Result<Json<PostsResponse>, StatusCode> {
     let page = params.page.unwrap_or(1);
     let limit = params.limit.unwrap_or(20);
     let offset = (page - 1) * limit;
 
-    let posts = if let Some(user_id) = params.user_id {
-        sqlx::query_as::<_, Post>(
-            "SELECT * FROM posts WHERE user_id = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3"
-        )
-        .bind(user_id)
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(&db.pool)
-        .await
-    } else {
-        sqlx::query_as::<_, Post>(
-            "SELECT * FROM posts ORDER BY created_at DESC LIMIT $1 OFFSET $2"
-        )
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(&db.pool)
-        .await
-    }
+    let posts = sqlx::query_as::<_, Post>(&format!(
+        "{} WHERE p.status = $4 AND ($5::text IS NULL OR p.user_id = $5) AND {} AND p.user_id NOT IN {}
+         ORDER BY p.published_at DESC LIMIT $6 OFFSET $7",
+        POST_SELECT,
+        tag_filter(content_type::POST, "p.id", "$8"),
+        hidden_users("$1")
+    ))
+    .bind(claims.as_ref().map(|c| c.sub.as_str()))
+    .bind(subscription_status::ENTITLED)
+    .bind(purchase_status::SETTLED)
+    .bind(post_status::PUBLISHED)
+    .bind(&params.user_id)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .bind(&params.tag)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let posts = with_attachments(&db, posts.into_iter().map(redact_locked).collect()).await?;
+
+    let total_count = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM posts p WHERE p.status = $1 AND ($2::text IS NULL OR p.user_id = $2) AND {} AND p.user_id NOT IN {}",
+        tag_filter(content_type::POST, "p.id", "$3"),
+        hidden_users("$4")
+    ))
+    .bind(post_status::PUBLISHED)
+    .bind(&params.user_id)
+    .bind(&params.tag)
+    .bind(claims.as_ref().map(|c| c.sub.as_str()))
+    .fetch_one(&db.pool)
+    .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     // Frontend'in beklediği format
-    let total = posts.len();
+    let total = total_count as usize;
     let response = PostsResponse {
         success: true,
         data: posts,
@@ -93,14 +170,21 @@ async fn get_posts_by_creator(
     State(db): State<Database>,
     Path(user_id): Path<String>,
     Query(params): Query<PostQuery>,
+    claims: Option<Claims>,
 ) -> Result<Json<PostsResponse>, StatusCode> {
     let page = params.page.unwrap_or(1);
     let limit = params.limit.unwrap_or(20);
     let offset = (page - 1) * limit;
 
-    let posts = sqlx::query_as::<_, Post>(
-        "SELECT * FROM posts WHERE user_id = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3"
-    )
+    let posts = sqlx::query_as::<_, Post>(&format!(
+        "{} WHERE p.status = $4 AND p.user_id = $5 AND p.user_id NOT IN {} ORDER BY p.published_at DESC LIMIT $6 OFFSET $7",
+        POST_SELECT,
+        blocked_users("$1")
+    ))
+    .bind(claims.as_ref().map(|c| c.sub.as_str()))
+    .bind(subscription_status::ENTITLED)
+    .bind(purchase_status::SETTLED)
+    .bind(post_status::PUBLISHED)
     .bind(&user_id)
     .bind(limit as i64)
     .bind(offset as i64)
@@ -110,11 +194,15 @@ async fn get_posts_by_creator(
         eprintln!("Error fetching posts: {:?}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
+    let posts = with_attachments(&db, posts.into_iter().map(redact_locked).collect()).await?;
 
-    let total_count = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM posts WHERE user_id = $1"
-    )
+    let total_count = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM posts WHERE user_id = $1 AND status = $2 AND user_id NOT IN {}",
+        blocked_users("$3")
+    ))
     .bind(&user_id)
+    .bind(post_status::PUBLISHED)
+    .bind(claims.as_ref().map(|c| c.sub.as_str()))
     .fetch_one(&db.pool)
     .await
     .map_err(|e| {
@@ -158,6 +246,7 @@ async fn get_my_posts(
         eprintln!("Error fetching my posts: {:?}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
+    let posts = with_attachments(&db, posts).await?;
 
     let total_count = sqlx::query_scalar::<_, i64>(
         "SELECT COUNT(*) FROM posts WHERE user_id = $1"
@@ -187,49 +276,100 @@ async fn get_my_posts(
 
 async fn create_post(
     State(db): State<Database>,
+    claims: Claims,
     Json(payload): Json<CreatePostRequest>,
-) -> Result<Json<Post>, StatusCode> {
-    // For now, use a default user_id for testing
-    let user_id = "550e8400-e29b-41d4-a716-446655440000".to_string();
+) -> Result<Json<PostWithAttachments>, StatusCode> {
+    let is_creator = db.is_creator(&claims.sub).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !is_creator {
+        return Err(StatusCode::FORBIDDEN);
+    }
 
-    println!("Creating post with payload: {:?}", payload);
+    validate_gates(&db, &claims.sub, &payload).await?;
+    if let Some(attachments) = &payload.attachments {
+        validate_attachments(attachments)?;
+    }
+    let (status, publish_at) = initial_status(&payload)?;
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     let post = sqlx::query_as::<_, Post>(
         r#"
-        INSERT INTO posts (user_id, title, content, media_url, media_type, is_premium)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO posts (user_id, title, content, media_url, media_type, is_premium, min_tier_id, product_id, preview, comments_enabled,
+                           status, publish_at, published_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, CASE WHEN $11 = $13 THEN NOW() END)
         RETURNING *
         "#
     )
-    .bind(user_id)
+    .bind(&claims.sub)
     .bind(&payload.title)
     .bind(&payload.content)
     .bind(&payload.media_url)
     .bind(&payload.media_type)
-    .bind(payload.is_premium.unwrap_or(false))
-    .fetch_one(&db.pool)
+    .bind(is_gated(&payload))
+    .bind(payload.min_tier_id)
+    .bind(payload.product_id)
+    .bind(&payload.preview)
+    .bind(payload.comments_enabled.unwrap_or(true))
+    .bind(status)
+    .bind(publish_at)
+    .bind(post_status::PUBLISHED)
+    .fetch_one(&mut tx)
     .await
     .map_err(|e| {
         eprintln!("Error creating post: {:?}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    Ok(Json(post))
+    if let Some(attachments) = &payload.attachments {
+        replace_attachments(&mut tx, post.id, attachments)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    if let Some(tags) = &payload.tags {
+        set_content_tags(&mut tx, &claims.sub, content_type::POST, &post.id.to_string(), tags).await?;
+    }
+
+    record_revision(&mut tx, post.id, &claims.sub)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if post.status == post_status::PUBLISHED {
+        notify_post_published(&mut tx, post.id, &post.title, &post.user_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(with_attachments_one(&db, post).await?))
 }
 
 async fn get_post_by_id(
     State(db): State<Database>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Post>, StatusCode> {
-    let post = sqlx::query_as::<_, Post>(
-        "SELECT * FROM posts WHERE id = $1"
-    )
+    claims: Option<Claims>,
+) -> Result<Json<PostDetail>, StatusCode> {
+    // Drafts and scheduled posts are only visible to their author, and posts
+    // are not shown across a block in either direction
+    let post = sqlx::query_as::<_, Post>(&format!(
+        "{} WHERE p.id = $4 AND (p.status = $5 OR p.user_id = $1) AND p.user_id NOT IN {}",
+        POST_SELECT,
+        blocked_users("$1")
+    ))
+    .bind(claims.as_ref().map(|c| c.sub.as_str()))
+    .bind(subscription_status::ENTITLED)
+    .bind(purchase_status::SETTLED)
     .bind(id)
+    .bind(post_status::PUBLISHED)
     .fetch_one(&db.pool)
     .await
     .map_err(|_| StatusCode::NOT_FOUND)?;
 
-    Ok(Json(post))
+    let poll = post_poll(&db, &post, claims.as_ref().map(|c| c.sub.as_str())).await?;
+    let post = with_attachments_one(&db, redact_locked(post)).await?;
+
+    Ok(Json(PostDetail { post, poll }))
 }
 
 async fn update_post(
@@ -237,27 +377,26 @@ async fn update_post(
     Path(id): Path<Uuid>,
     claims: Claims,
     Json(payload): Json<CreatePostRequest>,
-) -> Result<Json<Post>, StatusCode> {
-    let user_id = claims.sub.parse::<Uuid>().unwrap();
+) -> Result<Json<PostWithAttachments>, StatusCode> {
+    validate_gates(&db, &claims.sub, &payload).await?;
+    if let Some(attachments) = &payload.attachments {
+        validate_attachments(attachments)?;
+    }
 
-    // Check if user owns the post
-    let existing_post = sqlx::query_as::<_, Post>(
-        "SELECT * FROM posts WHERE id = $1 AND user_id = $2"
-    )
-    .bind(id)
-    .bind(user_id)
-    .fetch_optional(&db.pool)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    if existing_post.is_none() {
-        return Err(StatusCode::FORBIDDEN);
-    }
+    lock_own_post(&mut tx, id, &claims.sub).await?;
+
+    ensure_first_revision(&mut tx, id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     let post = sqlx::query_as::<_, Post>(
         r#"
         UPDATE posts 
-        SET title = $2, content = $3, media_url = $4, media_type = $5, is_premium = $6, updated_at = NOW()
+        SET title = $2, content = $3, media_url = $4, media_type = $5, is_premium = $6,
+            min_tier_id = $7, product_id = $8, preview = $9,
+            comments_enabled = COALESCE($10, comments_enabled), updated_at = NOW()
         WHERE id = $1
         RETURNING *
         "#
@@ -267,12 +406,32 @@ async fn update_post(
     .bind(&payload.content)
     .bind(&payload.media_url)
     .bind(&payload.media_type)
-    .bind(payload.is_premium.unwrap_or(false))
-    .fetch_one(&db.pool)
+    .bind(is_gated(&payload))
+    .bind(payload.min_tier_id)
+    .bind(payload.product_id)
+    .bind(&payload.preview)
+    .bind(payload.comments_enabled)
+    .fetch_one(&mut tx)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(post))
+    if let Some(attachments) = &payload.attachments {
+        replace_attachments(&mut tx, id, attachments)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    if let Some(tags) = &payload.tags {
+        set_content_tags(&mut tx, &claims.sub, content_type::POST, &id.to_string(), tags).await?;
+    }
+
+    record_revision(&mut tx, id, &claims.sub)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(with_attachments_one(&db, post).await?))
 }
 
 async fn delete_post(
@@ -280,14 +439,12 @@ async fn delete_post(
     Path(id): Path<Uuid>,
     claims: Claims,
 ) -> Result<StatusCode, StatusCode> {
-    let user_id = claims.sub.parse::<Uuid>().unwrap();
-
     // Check if user owns the post
     let existing_post = sqlx::query_as::<_, Post>(
         "SELECT * FROM posts WHERE id = $1 AND user_id = $2"
     )
     .bind(id)
-    .bind(user_id)
+    .bind(&claims.sub)
     .fetch_optional(&db.pool)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -296,11 +453,314 @@ async fn delete_post(
         return Err(StatusCode::FORBIDDEN);
     }
 
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
     sqlx::query("DELETE FROM posts WHERE id = $1")
         .bind(id)
-        .execute(&db.pool)
+        .execute(&mut tx)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    // Tag links are not foreign keys, so they go separately
+    sqlx::query("DELETE FROM content_tags WHERE content_type = $1 AND content_id = $2")
+        .bind(content_type::POST)
+        .bind(id.to_string())
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
     Ok(StatusCode::NO_CONTENT)
 }
+
+// Locked posts keep their metadata but only show a teaser
+pub(crate) fn redact_locked(mut post: Post) -> Post {
+    if post.locked {
+        let teaser = post.preview.clone().or_else(|| {
+            post.content
+                .as_deref()
+                .map(|content| content.chars().take(TEASER_LENGTH).collect())
+        });
+        post.content = teaser;
+        post.media_url = None;
+    }
+    post
+}
+
+async fn publish_post(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<PostWithAttachments>, StatusCode> {
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if lock_own_post(&mut tx, id, &claims.sub).await? == post_status::PUBLISHED {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let post = sqlx::query_as::<_, Post>(
+        "UPDATE posts SET status = $2, published_at = NOW(), updated_at = NOW() WHERE id = $1 RETURNING *"
+    )
+    .bind(id)
+    .bind(post_status::PUBLISHED)
+    .fetch_one(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    notify_post_published(&mut tx, post.id, &post.title, &post.user_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(with_attachments_one(&db, post).await?))
+}
+
+// Schedules a draft, or moves an already scheduled post
+async fn schedule_post(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<SchedulePostRequest>,
+) -> Result<Json<PostWithAttachments>, StatusCode> {
+    if payload.publish_at <= Utc::now() {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if lock_own_post(&mut tx, id, &claims.sub).await? == post_status::PUBLISHED {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let post = sqlx::query_as::<_, Post>(
+        "UPDATE posts SET status = $2, publish_at = $3, updated_at = NOW() WHERE id = $1 RETURNING *"
+    )
+    .bind(id)
+    .bind(post_status::SCHEDULED)
+    .bind(payload.publish_at)
+    .fetch_one(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(with_attachments_one(&db, post).await?))
+}
+
+// Cancelling a schedule turns the post back into a draft
+async fn unschedule_post(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<PostWithAttachments>, StatusCode> {
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if lock_own_post(&mut tx, id, &claims.sub).await? != post_status::SCHEDULED {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let post = sqlx::query_as::<_, Post>(
+        "UPDATE posts SET status = $2, publish_at = NULL, updated_at = NOW() WHERE id = $1 RETURNING *"
+    )
+    .bind(id)
+    .bind(post_status::DRAFT)
+    .fetch_one(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(with_attachments_one(&db, post).await?))
+}
+
+/// A post `viewer` may interact with: published, or their own, and not
+/// locked. Hidden posts are `NOT_FOUND`, gated ones `FORBIDDEN`.
+pub(crate) async fn fetch_readable_post(db: &Database, id: Uuid, viewer: Option<&str>) -> Result<Post, StatusCode> {
+    let post = sqlx::query_as::<_, Post>(&format!("{} WHERE p.id = $4", POST_SELECT))
+        .bind(viewer)
+        .bind(subscription_status::ENTITLED)
+        .bind(purchase_status::SETTLED)
+        .bind(id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if post.status != post_status::PUBLISHED && viewer != Some(post.user_id.as_str()) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    if post.locked {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(post)
+}
+
+/// Lock the caller's post and return its status. Holding the row lock keeps
+/// the scheduler from publishing it while the author changes it.
+pub(crate) async fn lock_own_post(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: Uuid,
+    user_id: &str,
+) -> Result<String, StatusCode> {
+    let (author_id, status) = sqlx::query_as::<_, (String, String)>(
+        "SELECT user_id, status FROM posts WHERE id = $1 FOR UPDATE"
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    if author_id != user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(status)
+}
+
+/// Publish scheduled posts whose time has come. The status change and the
+/// subscriber notifications commit together, so each post is announced once.
+pub(crate) async fn publish_due_posts(db: &Database) -> Result<usize, sqlx::Error> {
+    let mut tx = db.pool.begin().await?;
+
+    let published = sqlx::query_as::<_, (Uuid, String, String)>(
+        "UPDATE posts
+         SET status = $2, published_at = NOW(), updated_at = NOW()
+         WHERE id IN (
+             SELECT id FROM posts
+             WHERE status = $1 AND publish_at <= NOW()
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING id, title, user_id"
+    )
+    .bind(post_status::SCHEDULED)
+    .bind(post_status::PUBLISHED)
+    .fetch_all(&mut tx)
+    .await?;
+
+    for (id, title, user_id) in &published {
+        notify_post_published(&mut tx, *id, title, user_id).await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(published.len())
+}
+
+async fn notify_post_published(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: Uuid,
+    title: &str,
+    author_id: &str,
+) -> Result<u64, sqlx::Error> {
+    let notification = NewNotification {
+        kind: kind::POST_PUBLISHED,
+        title: format!("New post: {}", title),
+        message: None,
+        link: Some(format!("/posts/{}", id)),
+        actor_id: Some(author_id),
+    };
+
+    notifications::notify_creator_subscribers(tx, author_id, &notification).await
+}
+
+/// Status and publish time for a new post: published right away unless it is
+/// a draft or has a publish time, which must be in the future
+fn initial_status(payload: &CreatePostRequest) -> Result<(&'static str, Option<DateTime<Utc>>), StatusCode> {
+    match (payload.status.as_deref(), payload.publish_at) {
+        (None | Some(post_status::PUBLISHED), None) => Ok((post_status::PUBLISHED, None)),
+        (Some(post_status::DRAFT), None) => Ok((post_status::DRAFT, None)),
+        (None | Some(post_status::SCHEDULED), Some(at)) if at > Utc::now() => Ok((post_status::SCHEDULED, Some(at))),
+        _ => Err(StatusCode::UNPROCESSABLE_ENTITY),
+    }
+}
+
+// Requiring a tier or a purchase makes a post premium
+fn is_gated(payload: &CreatePostRequest) -> bool {
+    payload.is_premium.unwrap_or(false) || payload.min_tier_id.is_some() || payload.product_id.is_some()
+}
+
+/// Tiers and products a post is gated on must belong to its author
+async fn validate_gates(db: &Database, author_id: &str, payload: &CreatePostRequest) -> Result<(), StatusCode> {
+    let owns_gates = sqlx::query_scalar::<_, bool>(
+        "SELECT ($2::uuid IS NULL OR EXISTS(SELECT 1 FROM membership_tiers WHERE id = $2 AND creator_id = $1))
+            AND ($3::uuid IS NULL OR EXISTS(SELECT 1 FROM products WHERE id = $3 AND user_id = $1))"
+    )
+    .bind(author_id)
+    .bind(payload.min_tier_id)
+    .bind(payload.product_id)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if !owns_gates {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    Ok(())
+}
+
+async fn toggle_post_like(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    // Only posts the viewer can open can be liked, so drafts and gated posts stay hidden
+    let author_id = fetch_readable_post(&db, id, Some(&claims.sub)).await?.user_id;
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Lock the post so concurrent toggles keep the counter in step
+    sqlx::query("SELECT id FROM posts WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let unliked = sqlx::query("DELETE FROM post_likes WHERE post_id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(&claims.sub)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .rows_affected()
+        > 0;
+
+    if !unliked {
+        // Taking back a like still works after a block, giving a new one does not
+        if is_blocked_between(&mut tx, &claims.sub, &author_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        {
+            return Err(StatusCode::FORBIDDEN);
+        }
+
+        sqlx::query("INSERT INTO post_likes (post_id, user_id) VALUES ($1, $2)")
+            .bind(id)
+            .bind(&claims.sub)
+            .execute(&mut tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    let like_count = sqlx::query_scalar::<_, i32>(
+        "UPDATE posts SET like_count = GREATEST(like_count + $2, 0) WHERE id = $1 RETURNING like_count"
+    )
+    .bind(id)
+    .bind(if unliked { -1 } else { 1 })
+    .fetch_one(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "liked": !unliked,
+        "data": { "likeCount": like_count }
+    })))
+}
@@ -6,20 +6,42 @@ use axum::{
     Router,
 };
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use uuid::Uuid;
 
 use crate::{
     auth::Claims,
     database::Database,
-    models::{CreateProductRequest, Product},
+    models::{content_type, purchase_status, CreateProductRequest, Product},
+    routes::{
+        reviews::review_routes,
+        tags::{set_content_tags, tag_filter},
+    },
 };
 
+// Storefront aggregates are recomputed at most this often
+const PRODUCT_STATS_TTL: Duration = Duration::from_secs(60);
+pub(crate) const PRODUCT_CACHE_PREFIX: &str = "products:";
+
+#[derive(Debug, Deserialize)]
+pub struct CollectionsQuery {
+    pub trending_days: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeaturedRequest {
+    pub featured: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ProductQuery {
     pub page: Option<u32>,
     pub limit: Option<u32>,
-    pub user_id: Option<Uuid>,
+    pub user_id: Option<String>,
     pub creatorId: Option<String>,
+    pub sort: Option<String>,
+    /// Only products carrying a tag with this slug
+    pub tag: Option<String>,
 }
 
 pub fn product_routes() -> Router<Database> {
@@ -30,6 +52,18 @@ pub fn product_routes() -> Router<Database> {
         .route("/:id", get(get_product_by_id))
         .route("/:id", put(update_product))
         .route("/:id", delete(delete_product))
+        .route("/:id/featured", put(set_product_featured))
+        .route("/:id/download", get(get_product_download))
+        .merge(review_routes())
+}
+
+fn product_order_by(sort: Option<&str>) -> &'static str {
+    match sort {
+        Some("rating") => "rating_average DESC NULLS LAST, rating_count DESC, created_at DESC",
+        Some("price_asc") => "price ASC, created_at DESC",
+        Some("price_desc") => "price DESC, created_at DESC",
+        _ => "created_at DESC",
+    }
 }
 
 async fn get_products(
@@ -40,33 +74,21 @@ async fn get_products(
     let limit = params.limit.unwrap_or(20);
     let offset = (page - 1) * limit;
 
-    let products = if let Some(creator_id) = params.creatorId {
-        sqlx::query_as::<_, Product>(
-            "SELECT * FROM products WHERE user_id = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3"
-        )
-        .bind(&creator_id)
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(&db.pool)
-        .await
-    } else if let Some(user_id) = params.user_id {
-        sqlx::query_as::<_, Product>(
-            "SELECT * FROM products WHERE user_id = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3"
-        )
-        .bind(user_id)
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(&db.pool)
-        .await
-    } else {
-        sqlx::query_as::<_, Product>(
-            "SELECT * FROM products ORDER BY created_at DESC LIMIT $1 OFFSET $2"
-        )
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(&db.pool)
-        .await
-    }
+    let order_by = product_order_by(params.sort.as_deref());
+
+    let creator_id = params.creatorId.or(params.user_id);
+
+    let products = sqlx::query_as::<_, Product>(&format!(
+        "SELECT * FROM products pr WHERE ($1::text IS NULL OR pr.user_id = $1) AND {} ORDER BY {} LIMIT $3 OFFSET $4",
+        tag_filter(content_type::PRODUCT, "pr.id", "$2"),
+        order_by
+    ))
+    .bind(&creator_id)
+    .bind(&params.tag)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(&db.pool)
+    .await
     .map_err(|e| {
         eprintln!("Error fetching products: {:?}", e);
         StatusCode::INTERNAL_SERVER_ERROR
@@ -80,16 +102,21 @@ async fn create_product(
     claims: Claims,
     Json(payload): Json<CreateProductRequest>,
 ) -> Result<Json<Product>, StatusCode> {
-    let user_id = claims.sub.parse::<Uuid>().unwrap();
+    let is_creator = db.is_creator(&claims.sub).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !is_creator {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     let product = sqlx::query_as::<_, Product>(
         r#"
-        INSERT INTO products (user_id, name, description, price, currency, image_url, is_digital, download_url)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        INSERT INTO products (user_id, name, description, price, currency, image_url, is_digital, download_url, featured)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         RETURNING *
         "#
     )
-    .bind(user_id)
+    .bind(&claims.sub)
     .bind(&payload.name)
     .bind(&payload.description)
     .bind(&payload.price)
@@ -97,10 +124,19 @@ async fn create_product(
     .bind(&payload.image_url)
     .bind(payload.is_digital.unwrap_or(false))
     .bind(&payload.download_url)
-    .fetch_one(&db.pool)
+    .bind(payload.featured.unwrap_or(false))
+    .fetch_one(&mut tx)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    if let Some(tags) = &payload.tags {
+        set_content_tags(&mut tx, &claims.sub, content_type::PRODUCT, &product.id.to_string(), tags).await?;
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+
     Ok(Json(product))
 }
 
@@ -125,14 +161,12 @@ async fn update_product(
     claims: Claims,
     Json(payload): Json<CreateProductRequest>,
 ) -> Result<Json<Product>, StatusCode> {
-    let user_id = claims.sub.parse::<Uuid>().unwrap();
-
     // Check if user owns the product
     let existing_product = sqlx::query_as::<_, Product>(
         "SELECT * FROM products WHERE id = $1 AND user_id = $2"
     )
     .bind(id)
-    .bind(user_id)
+    .bind(&claims.sub)
     .fetch_optional(&db.pool)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -141,10 +175,13 @@ async fn update_product(
         return Err(StatusCode::FORBIDDEN);
     }
 
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
     let product = sqlx::query_as::<_, Product>(
         r#"
         UPDATE products 
-        SET name = $2, description = $3, price = $4, currency = $5, image_url = $6, is_digital = $7, download_url = $8, updated_at = NOW()
+        SET name = $2, description = $3, price = $4, currency = $5, image_url = $6, is_digital = $7, download_url = $8,
+            featured = COALESCE($9, featured), updated_at = NOW()
         WHERE id = $1
         RETURNING *
         "#
@@ -157,10 +194,19 @@ async fn update_product(
     .bind(&payload.image_url)
     .bind(payload.is_digital.unwrap_or(false))
     .bind(&payload.download_url)
-    .fetch_one(&db.pool)
+    .bind(payload.featured)
+    .fetch_one(&mut tx)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    if let Some(tags) = &payload.tags {
+        set_content_tags(&mut tx, &claims.sub, content_type::PRODUCT, &id.to_string(), tags).await?;
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+
     Ok(Json(product))
 }
 
@@ -169,14 +215,12 @@ async fn delete_product(
     Path(id): Path<Uuid>,
     claims: Claims,
 ) -> Result<StatusCode, StatusCode> {
-    let user_id = claims.sub.parse::<Uuid>().unwrap();
-
     // Check if user owns the product
     let existing_product = sqlx::query_as::<_, Product>(
         "SELECT * FROM products WHERE id = $1 AND user_id = $2"
     )
     .bind(id)
-    .bind(user_id)
+    .bind(&claims.sub)
     .fetch_optional(&db.pool)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -185,20 +229,96 @@ async fn delete_product(
         return Err(StatusCode::FORBIDDEN);
     }
 
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
     sqlx::query("DELETE FROM products WHERE id = $1")
         .bind(id)
-        .execute(&db.pool)
+        .execute(&mut tx)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    sqlx::query("DELETE FROM content_tags WHERE content_type = $1 AND content_id = $2")
+        .bind(content_type::PRODUCT)
+        .bind(id.to_string())
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+
     Ok(StatusCode::NO_CONTENT)
 }
 
-#[derive(Debug, Serialize)]
-struct ProductMeta {
-    types: Vec<TypeCount>,
-    price_range: PriceRange,
-    stats: ProductStats,
+async fn set_product_featured(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<FeaturedRequest>,
+) -> Result<Json<Product>, StatusCode> {
+    // Only the owning creator may feature their product
+    let product = sqlx::query_as::<_, Product>(
+        "UPDATE products SET featured = $3, updated_at = NOW() WHERE id = $1 AND user_id = $2 RETURNING *"
+    )
+    .bind(id)
+    .bind(&claims.sub)
+    .bind(payload.featured)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error updating featured flag: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::FORBIDDEN)?;
+
+    db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+
+    Ok(Json(product))
+}
+
+async fn get_product_download(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let product = sqlx::query_as::<_, Product>(
+        "SELECT * FROM products WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    // Refunded or revoked purchases lose download access
+    let has_access = product.user_id == claims.sub
+        || sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM purchases
+                           WHERE product_id = $1 AND user_id = $2 AND status = ANY($3) AND access_revoked_at IS NULL)"
+        )
+        .bind(id)
+        .bind(&claims.sub)
+        .bind(purchase_status::SETTLED)
+        .fetch_one(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if !has_access {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let file_url = product.download_url.ok_or(StatusCode::NOT_FOUND)?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "data": {
+            "fileUrl": file_url,
+            "fileName": product.name
+        }
+    });
+
+    Ok(Json(response))
 }
 
 #[derive(Debug, Serialize, sqlx::FromRow)]
@@ -207,23 +327,32 @@ struct TypeCount {
     count: i64,
 }
 
-#[derive(Debug, Serialize)]
-struct PriceRange {
-    min: f64,
-    max: f64,
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+struct CurrencyRevenue {
+    currency: String,
+    units_sold: i64,
+    revenue: f64,
 }
 
-#[derive(Debug, Serialize)]
-struct ProductStats {
-    total_products: i64,
-    featured_count: i64,
-    creator_count: i64,
-    total_revenue: f64,
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+struct ProductWithSales {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    product: Product,
+    units_sold: i64,
+    revenue: f64,
 }
 
 async fn get_products_meta(
     State(db): State<Database>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
+    let cache_key = format!("{}meta", PRODUCT_CACHE_PREFIX);
+    if let Some(cached) = db.cache.get(&cache_key) {
+        return Ok(Json(cached));
+    }
+
     // Get product types and counts
     let types = sqlx::query_as::<_, TypeCount>(
         "SELECT 'DIGITAL' as type, COUNT(*) as count FROM products WHERE is_digital = true"
@@ -240,19 +369,37 @@ async fn get_products_meta(
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    // Get stats
-    let stats = sqlx::query_as::<_, (i64, i64, i64, f64)>(
+    // Get catalogue stats
+    let stats = sqlx::query_as::<_, (i64, i64, i64)>(
         "SELECT 
             COUNT(*) as total_products,
-            COUNT(CASE WHEN is_digital = true THEN 1 END) as featured_count,
-            COUNT(DISTINCT user_id) as creator_count,
-            COALESCE(SUM(price), 0) as total_revenue
+            COUNT(CASE WHEN featured = true THEN 1 END) as featured_count,
+            COUNT(DISTINCT user_id) as creator_count
          FROM products"
     )
     .fetch_one(&db.pool)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    // Sales come from settled purchases (one unit per row), net of refunds
+    let revenue_by_currency = sqlx::query_as::<_, CurrencyRevenue>(
+        "SELECT COALESCE(currency, 'USD') as currency, COUNT(*) as units_sold, COALESCE(SUM(amount - refunded_amount), 0)::float8 as revenue
+         FROM purchases
+         WHERE status = ANY($1)
+         GROUP BY COALESCE(currency, 'USD')
+         ORDER BY revenue DESC"
+    )
+    .bind(purchase_status::SETTLED)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error aggregating product revenue: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let units_sold: i64 = revenue_by_currency.iter().map(|r| r.units_sold).sum();
+    let total_revenue: f64 = revenue_by_currency.iter().map(|r| r.revenue).sum();
+
     let response = serde_json::json!({
         "success": true,
         "data": {
@@ -265,39 +412,78 @@ async fn get_products_meta(
                 "totalProducts": stats.0,
                 "featuredCount": stats.1,
                 "creatorCount": stats.2,
-                "totalRevenue": stats.3
+                "unitsSold": units_sold,
+                "totalRevenue": total_revenue,
+                "revenueByCurrency": revenue_by_currency
             }
         }
     });
 
-    Ok(Json(response))
-}
+    db.cache.set(&cache_key, response.clone(), PRODUCT_STATS_TTL);
 
-#[derive(Debug, Serialize)]
-struct ProductCollections {
-    featured: Vec<Product>,
-    top_selling: Vec<Product>,
-    new_arrivals: Vec<Product>,
+    Ok(Json(response))
 }
 
 async fn get_products_collections(
     State(db): State<Database>,
+    Query(params): Query<CollectionsQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    // Get featured products (digital products)
+    let trending_days = params.trending_days.unwrap_or(7).clamp(1, 90);
+    let cache_key = format!("{}collections:{}", PRODUCT_CACHE_PREFIX, trending_days);
+    if let Some(cached) = db.cache.get(&cache_key) {
+        return Ok(Json(cached));
+    }
+
+    // Get featured products (flagged by their creator)
     let featured = sqlx::query_as::<_, Product>(
-        "SELECT * FROM products WHERE is_digital = true ORDER BY created_at DESC LIMIT 6"
+        "SELECT * FROM products WHERE featured = true ORDER BY updated_at DESC LIMIT 6"
     )
     .fetch_all(&db.pool)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    // Get top selling products (by price, as we don't have sales data)
-    let top_selling = sqlx::query_as::<_, Product>(
-        "SELECT * FROM products ORDER BY price DESC LIMIT 6"
+    // Get top selling products over all time
+    let top_selling = sqlx::query_as::<_, ProductWithSales>(
+        "SELECT p.*, s.units_sold, s.revenue
+         FROM products p
+         JOIN (
+            SELECT product_id, COUNT(*) as units_sold, COALESCE(SUM(amount - refunded_amount), 0)::float8 as revenue
+            FROM purchases
+            WHERE status = ANY($1)
+            GROUP BY product_id
+         ) s ON s.product_id = p.id
+         ORDER BY s.units_sold DESC, s.revenue DESC
+         LIMIT 6"
     )
+    .bind(purchase_status::SETTLED)
     .fetch_all(&db.pool)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .map_err(|e| {
+        eprintln!("Error fetching top selling products: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    // Get trending products (sales within the window)
+    let trending = sqlx::query_as::<_, ProductWithSales>(
+        "SELECT p.*, s.units_sold, s.revenue
+         FROM products p
+         JOIN (
+            SELECT product_id, COUNT(*) as units_sold, COALESCE(SUM(amount - refunded_amount), 0)::float8 as revenue
+            FROM purchases
+            WHERE status = ANY($1) AND created_at >= NOW() - make_interval(days => $2)
+            GROUP BY product_id
+         ) s ON s.product_id = p.id
+         ORDER BY s.units_sold DESC, s.revenue DESC
+         LIMIT 6"
+    )
+    .bind(purchase_status::SETTLED)
+    .bind(trending_days)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching trending products: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
     // Get new arrivals
     let new_arrivals = sqlx::query_as::<_, Product>(
@@ -312,9 +498,13 @@ async fn get_products_collections(
         "data": {
             "featured": featured,
             "topSelling": top_selling,
+            "trending": trending,
+            "trendingDays": trending_days,
             "newArrivals": new_arrivals
         }
     });
 
+    db.cache.set(&cache_key, response.clone(), PRODUCT_STATS_TTL);
+
     Ok(Json(response))
 }
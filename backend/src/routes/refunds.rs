@@ -0,0 +1,614 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    config::Config,
+    database::Database,
+    models::{purchase_status, subscription_status},
+    payments::{to_minor_units, StripeClient},
+    routes::{products::PRODUCT_CACHE_PREFIX, rewards::release_reward},
+};
+
+// Refund request states stored in `refunds.status`
+const REFUND_PENDING: &str = "PENDING";
+const REFUND_SUCCEEDED: &str = "SUCCEEDED";
+const REFUND_FAILED: &str = "FAILED";
+
+// Tolerance for floating point comparisons on money amounts
+const AMOUNT_EPSILON: f64 = 0.005;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RefundTarget {
+    Purchase,
+    Subscription,
+    Donation,
+}
+
+impl RefundTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RefundTarget::Purchase => "purchase",
+            RefundTarget::Subscription => "subscription",
+            RefundTarget::Donation => "donation",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "purchase" => Some(RefundTarget::Purchase),
+            "subscription" => Some(RefundTarget::Subscription),
+            "donation" => Some(RefundTarget::Donation),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn table(&self) -> &'static str {
+        match self {
+            RefundTarget::Purchase => "purchases",
+            RefundTarget::Subscription => "subscriptions",
+            RefundTarget::Donation => "donations",
+        }
+    }
+
+    /// Purchases and donations move through the settlement states in
+    /// `purchase_status`; subscriptions keep Stripe's own status.
+    pub(crate) fn uses_settlement_status(&self) -> bool {
+        !matches!(self, RefundTarget::Subscription)
+    }
+
+    // Loads the charge behind a refundable record and locks it for the transaction
+    pub(crate) fn select_for_update_sql(&self) -> &'static str {
+        match self {
+            RefundTarget::Purchase => {
+                "SELECT pu.id, pr.user_id AS payee_id, pu.stripe_payment_intent_id AS payment_intent_id,
+                        pu.amount::float8 AS amount, pu.refunded_amount::float8 AS refunded_amount,
+                        COALESCE(pu.currency, 'USD') AS currency, pu.status
+                 FROM purchases pu
+                 JOIN products pr ON pr.id = pu.product_id
+                 WHERE pu.id = $1
+                 FOR UPDATE OF pu"
+            }
+            RefundTarget::Subscription => {
+                "SELECT id, creator_id AS payee_id, last_payment_intent_id AS payment_intent_id,
+                        COALESCE(last_charge_amount, 0) AS amount, refunded_amount,
+                        COALESCE(last_charge_currency, 'USD') AS currency, status
+                 FROM subscriptions
+                 WHERE id = $1
+                 FOR UPDATE"
+            }
+            RefundTarget::Donation => {
+                "SELECT d.id, c.creator_id AS payee_id, d.stripe_payment_intent_id AS payment_intent_id,
+                        d.amount, d.refunded_amount, COALESCE(d.currency, 'USD') AS currency, d.status
+                 FROM donations d
+                 JOIN campaigns c ON c.id = d.campaign_id
+                 WHERE d.id = $1
+                 FOR UPDATE OF d"
+            }
+        }
+    }
+
+    pub(crate) fn set_refunded_amount_sql(&self) -> &'static str {
+        match self {
+            RefundTarget::Purchase => "UPDATE purchases SET refunded_amount = $2 WHERE id = $1",
+            RefundTarget::Subscription => "UPDATE subscriptions SET refunded_amount = $2, updated_at = NOW() WHERE id = $1",
+            RefundTarget::Donation => "UPDATE donations SET refunded_amount = $2, updated_at = NOW() WHERE id = $1",
+        }
+    }
+
+    fn is_refundable(&self, status: &str) -> bool {
+        match self {
+            RefundTarget::Purchase | RefundTarget::Donation => purchase_status::SETTLED.contains(&status),
+            RefundTarget::Subscription => status != subscription_status::CANCELED,
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct Charge {
+    pub id: Uuid,
+    pub payee_id: String,
+    pub payment_intent_id: Option<String>,
+    pub amount: f64,
+    pub refunded_amount: f64,
+    pub currency: String,
+    pub status: String,
+}
+
+impl Charge {
+    pub(crate) fn is_fully_refunded(&self) -> bool {
+        self.refunded_amount + AMOUNT_EPSILON >= self.amount
+    }
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct Refund {
+    pub id: Uuid,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub amount: f64,
+    pub currency: Option<String>,
+    pub reason: Option<String>,
+    pub status: String,
+    pub stripe_refund_id: Option<String>,
+    pub failure_reason: Option<String>,
+    pub initiated_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRefundRequest {
+    pub target_type: RefundTarget,
+    pub target_id: Uuid,
+    /// Omit for a full refund of whatever has not been refunded yet
+    pub amount: Option<f64>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RefundQuery {
+    pub target_type: RefundTarget,
+    pub target_id: Uuid,
+}
+
+/// One row of the payment audit trail
+pub(crate) struct AuditEntry<'a> {
+    pub target: &'a str,
+    pub target_id: Uuid,
+    pub action: &'a str,
+    pub from_status: Option<&'a str>,
+    pub to_status: Option<&'a str>,
+    pub amount: Option<f64>,
+    pub actor_id: Option<&'a str>,
+    pub details: Option<String>,
+}
+
+pub fn refund_routes() -> Router<Database> {
+    Router::new()
+        .route("/", get(get_refunds).post(create_refund))
+        .route("/:id", get(get_refund_by_id))
+}
+
+async fn create_refund(
+    State(db): State<Database>,
+    claims: Claims,
+    Json(payload): Json<CreateRefundRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let target = payload.target_type;
+    let is_admin = db.is_admin(&claims.sub).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Reserve the amount first so concurrent refunds cannot exceed the charge
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let charge = sqlx::query_as::<_, Charge>(target.select_for_update_sql())
+        .bind(payload.target_id)
+        .fetch_optional(&mut tx)
+        .await
+        .map_err(|e| {
+            eprintln!("Error loading {} for refund: {:?}", target.as_str(), e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // Creators refund their own sales, admins can refund anything
+    if charge.payee_id != claims.sub && !is_admin {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    if !target.is_refundable(&charge.status) {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let remaining = charge.amount - charge.refunded_amount;
+    let amount = payload.amount.unwrap_or(remaining);
+    if amount <= 0.0 || amount > remaining + AMOUNT_EPSILON {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    sqlx::query(target.set_refunded_amount_sql())
+        .bind(charge.id)
+        .bind(charge.refunded_amount + amount)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let refund_id = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO refunds (target_type, target_id, amount, currency, reason, status, initiated_by)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         RETURNING id"
+    )
+    .bind(target.as_str())
+    .bind(charge.id)
+    .bind(amount)
+    .bind(&charge.currency)
+    .bind(&payload.reason)
+    .bind(REFUND_PENDING)
+    .bind(&claims.sub)
+    .fetch_one(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating refund: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    record_payment_audit(&mut tx, AuditEntry {
+        target: target.as_str(),
+        target_id: charge.id,
+        action: "refund_requested",
+        from_status: Some(&charge.status),
+        to_status: None,
+        amount: Some(amount),
+        actor_id: Some(&claims.sub),
+        details: payload.reason.clone(),
+    })
+    .await?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Charges without a payment intent were settled outside Stripe, so there is nothing to reverse
+    let gateway_result = match &charge.payment_intent_id {
+        Some(payment_intent_id) => {
+            let config = Config::from_env().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            match StripeClient::from_config(&config) {
+                Ok(stripe) => stripe
+                    .create_refund(payment_intent_id, Some(to_minor_units(amount, &charge.currency)), payload.reason.as_deref())
+                    .await
+                    .map(|refund| Some(refund.id))
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        }
+        None => Ok(None),
+    };
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let charge = sqlx::query_as::<_, Charge>(target.select_for_update_sql())
+        .bind(charge.id)
+        .fetch_one(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    match gateway_result {
+        Ok(stripe_refund_id) => {
+            sqlx::query("UPDATE refunds SET status = $2, stripe_refund_id = $3, updated_at = NOW() WHERE id = $1")
+                .bind(refund_id)
+                .bind(REFUND_SUCCEEDED)
+                .bind(&stripe_refund_id)
+                .execute(&mut tx)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let to_status = apply_refunded_state(&mut tx, target, &charge, amount).await?;
+
+            record_payment_audit(&mut tx, AuditEntry {
+                target: target.as_str(),
+                target_id: charge.id,
+                action: "refund_succeeded",
+                from_status: Some(&charge.status),
+                to_status: Some(&to_status),
+                amount: Some(amount),
+                actor_id: Some(&claims.sub),
+                details: stripe_refund_id,
+            })
+            .await?;
+        }
+        Err(error) => {
+            eprintln!("Refund {} failed at the payment gateway: {}", refund_id, error);
+
+            sqlx::query("UPDATE refunds SET status = $2, failure_reason = $3, updated_at = NOW() WHERE id = $1")
+                .bind(refund_id)
+                .bind(REFUND_FAILED)
+                .bind(&error)
+                .execute(&mut tx)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            // Release the reservation
+            sqlx::query(target.set_refunded_amount_sql())
+                .bind(charge.id)
+                .bind((charge.refunded_amount - amount).max(0.0))
+                .execute(&mut tx)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            record_payment_audit(&mut tx, AuditEntry {
+                target: target.as_str(),
+                target_id: charge.id,
+                action: "refund_failed",
+                from_status: Some(&charge.status),
+                to_status: Some(&charge.status),
+                amount: Some(amount),
+                actor_id: Some(&claims.sub),
+                details: Some(error),
+            })
+            .await?;
+        }
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if target == RefundTarget::Purchase {
+        db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+    }
+
+    let refund = fetch_refund(&db, refund_id).await?;
+    if refund.status == REFUND_SUCCEEDED && target == RefundTarget::Subscription && charge.is_fully_refunded() {
+        cancel_refunded_subscription(&db, charge.id, Some(&claims.sub)).await?;
+    }
+
+    if refund.status == REFUND_FAILED {
+        return Err(StatusCode::BAD_GATEWAY);
+    }
+
+    let response = serde_json::json!({
+        "success": true,
+        "data": refund
+    });
+
+    Ok(Json(response))
+}
+
+async fn get_refunds(
+    State(db): State<Database>,
+    claims: Claims,
+    Query(params): Query<RefundQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_can_view(&db, params.target_type, params.target_id, &claims.sub).await?;
+
+    let refunds = sqlx::query_as::<_, Refund>(
+        "SELECT * FROM refunds WHERE target_type = $1 AND target_id = $2 ORDER BY created_at DESC"
+    )
+    .bind(params.target_type.as_str())
+    .bind(params.target_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "data": refunds
+    });
+
+    Ok(Json(response))
+}
+
+async fn get_refund_by_id(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let refund = fetch_refund(&db, id).await?;
+    let target = RefundTarget::parse(&refund.target_type).ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    ensure_can_view(&db, target, refund.target_id, &claims.sub).await?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "data": refund
+    });
+
+    Ok(Json(response))
+}
+
+async fn fetch_refund(db: &Database, id: Uuid) -> Result<Refund, StatusCode> {
+    sqlx::query_as::<_, Refund>("SELECT * FROM refunds WHERE id = $1")
+        .bind(id)
+        .fetch_one(&db.pool)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)
+}
+
+// Payer, payee and admins may see the refund history of a charge
+async fn ensure_can_view(db: &Database, target: RefundTarget, target_id: Uuid, user_id: &str) -> Result<(), StatusCode> {
+    let query = match target {
+        RefundTarget::Purchase => {
+            "SELECT EXISTS(SELECT 1 FROM purchases pu JOIN products pr ON pr.id = pu.product_id
+                           WHERE pu.id = $1 AND (pu.user_id = $2 OR pr.user_id = $2))"
+        }
+        RefundTarget::Subscription => {
+            "SELECT EXISTS(SELECT 1 FROM subscriptions WHERE id = $1 AND (user_id = $2 OR creator_id = $2))"
+        }
+        RefundTarget::Donation => {
+            "SELECT EXISTS(SELECT 1 FROM donations d JOIN campaigns c ON c.id = d.campaign_id
+                           WHERE d.id = $1 AND (d.user_id = $2 OR c.creator_id = $2))"
+        }
+    };
+
+    let allowed = sqlx::query_scalar::<_, bool>(query)
+        .bind(target_id)
+        .bind(user_id)
+        .fetch_one(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if allowed || db.is_admin(user_id).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+/// Moves a charge to its post-refund state based on `refunded_amount` and
+/// revokes access once it is fully refunded. `refunded_delta` is the amount
+/// refunded by this change. Returns the new status.
+pub(crate) async fn apply_refunded_state(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    target: RefundTarget,
+    charge: &Charge,
+    refunded_delta: f64,
+) -> Result<String, StatusCode> {
+    let fully_refunded = charge.is_fully_refunded();
+
+    if target == RefundTarget::Donation && refunded_delta > 0.0 {
+        // Refunded money no longer counts towards the campaign total
+        sqlx::query(
+            "UPDATE campaigns SET current_amount = GREATEST(COALESCE(current_amount, 0) - $2, 0), updated_at = NOW()
+             WHERE id = (SELECT campaign_id FROM donations WHERE id = $1)"
+        )
+        .bind(charge.id)
+        .bind(refunded_delta)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    match target {
+        RefundTarget::Purchase | RefundTarget::Donation => {
+            let to_status = if fully_refunded {
+                purchase_status::REFUNDED
+            } else {
+                purchase_status::PARTIALLY_REFUNDED
+            };
+
+            if !purchase_status::can_transition(&charge.status, to_status) {
+                return Ok(charge.status.clone());
+            }
+
+            sqlx::query(&format!("UPDATE {} SET status = $2 WHERE id = $1", target.table()))
+                .bind(charge.id)
+                .bind(to_status)
+                .execute(&mut *tx)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            // A full refund also revokes downloads and any license tied to the purchase
+            if target == RefundTarget::Purchase && fully_refunded {
+                sqlx::query("UPDATE purchases SET access_revoked_at = COALESCE(access_revoked_at, NOW()) WHERE id = $1")
+                    .bind(charge.id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            }
+
+            // A fully refunded pledge gives up its reward unless it already shipped
+            if target == RefundTarget::Donation && fully_refunded {
+                release_reward(tx, charge.id)
+                    .await
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            }
+
+            Ok(to_status.to_string())
+        }
+        // Subscriptions are only canceled once Stripe has stopped billing them,
+        // which happens outside the transaction in `cancel_refunded_subscription`
+        RefundTarget::Subscription => Ok(charge.status.clone()),
+    }
+}
+
+/// Ends a fully refunded subscription: cancels it in Stripe first and only
+/// then marks it canceled locally. If Stripe refuses, the local status stays
+/// as it is so the subscription is not shown as canceled while still billing.
+pub(crate) async fn cancel_refunded_subscription(
+    db: &Database,
+    subscription_id: Uuid,
+    actor_id: Option<&str>,
+) -> Result<(), StatusCode> {
+    let (status, stripe_subscription_id) = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT status, stripe_subscription_id FROM subscriptions WHERE id = $1"
+    )
+    .bind(subscription_id)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if status == subscription_status::CANCELED {
+        return Ok(());
+    }
+
+    // Subscriptions without a Stripe id are billed outside Stripe
+    if let Some(stripe_subscription_id) = &stripe_subscription_id {
+        let config = Config::from_env().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let canceled = match StripeClient::from_config(&config) {
+            Ok(stripe) => stripe
+                .cancel_subscription(stripe_subscription_id)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        if let Err(error) = canceled {
+            eprintln!("Could not cancel refunded subscription {}: {}", subscription_id, error);
+
+            let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            record_payment_audit(&mut tx, AuditEntry {
+                target: RefundTarget::Subscription.as_str(),
+                target_id: subscription_id,
+                action: "subscription_cancel_failed",
+                from_status: Some(&status),
+                to_status: Some(&status),
+                amount: None,
+                actor_id,
+                details: Some(error),
+            })
+            .await?;
+            tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            return Ok(());
+        }
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Refunding the charge ends the paid period immediately
+    sqlx::query(
+        "UPDATE subscriptions SET status = $2, current_period_end = NOW(), updated_at = NOW() WHERE id = $1"
+    )
+    .bind(subscription_id)
+    .bind(subscription_status::CANCELED)
+    .execute(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    record_payment_audit(&mut tx, AuditEntry {
+        target: RefundTarget::Subscription.as_str(),
+        target_id: subscription_id,
+        action: "subscription_canceled",
+        from_status: Some(&status),
+        to_status: Some(subscription_status::CANCELED),
+        amount: None,
+        actor_id,
+        details: stripe_subscription_id,
+    })
+    .await?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(())
+}
+
+pub(crate) async fn record_payment_audit(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    entry: AuditEntry<'_>,
+) -> Result<(), StatusCode> {
+    sqlx::query(
+        "INSERT INTO payment_audit_log (target_type, target_id, action, from_status, to_status, amount, actor_id, details)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
+    )
+    .bind(entry.target)
+    .bind(entry.target_id)
+    .bind(entry.action)
+    .bind(entry.from_status)
+    .bind(entry.to_status)
+    .bind(entry.amount)
+    .bind(entry.actor_id)
+    .bind(entry.details)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error writing payment audit log: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,263 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, put},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::{report_reason, report_status, report_target},
+};
+
+const MAX_DETAILS_LENGTH: usize = 2000;
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub id: Uuid,
+    pub reporter_id: String,
+    pub reporter_username: Option<String>,
+    pub target_type: String,
+    pub target_id: String,
+    pub target_owner_id: Option<String>,
+    pub reason: String,
+    pub details: Option<String>,
+    pub status: String,
+    /// Open reports on the same target, including this one
+    pub open_reports_on_target: i64,
+    pub resolved_by: Option<String>,
+    pub resolution_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReportRequest {
+    pub target_type: String,
+    pub target_id: String,
+    pub reason: String,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveReportRequest {
+    /// `RESOLVED` or `DISMISSED`
+    pub status: String,
+    pub resolution_note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportQuery {
+    pub status: Option<String>,
+    pub target_type: Option<String>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+const REPORT_SELECT: &str = "SELECT r.id, r.reporter_id, u.username AS reporter_username, r.target_type, r.target_id,
+            r.target_owner_id, r.reason, r.details, r.status,
+            (SELECT COUNT(*) FROM reports o
+             WHERE o.target_type = r.target_type AND o.target_id = r.target_id AND o.status = 'OPEN') AS open_reports_on_target,
+            r.resolved_by, r.resolution_note, r.created_at, r.resolved_at
+     FROM reports r
+     LEFT JOIN users u ON u.id = r.reporter_id";
+
+/// Routes mounted under `/api/reports`
+pub fn report_routes() -> Router<Database> {
+    Router::new()
+        .route("/", get(get_report_queue).post(create_report))
+        .route("/:id", put(resolve_report))
+}
+
+async fn create_report(
+    State(db): State<Database>,
+    claims: Claims,
+    Json(payload): Json<CreateReportRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if !report_reason::is_valid(&payload.reason)
+        || payload.details.as_deref().is_some_and(|d| d.chars().count() > MAX_DETAILS_LENGTH)
+    {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let owner_id = target_owner(&db, &payload.target_type, &payload.target_id).await?;
+
+    // Reporting yourself or your own content has no one to act on
+    if owner_id == claims.sub {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO reports (reporter_id, target_type, target_id, target_owner_id, reason, details)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT DO NOTHING
+         RETURNING id"
+    )
+    .bind(&claims.sub)
+    .bind(&payload.target_type)
+    .bind(&payload.target_id)
+    .bind(&owner_id)
+    .bind(&payload.reason)
+    .bind(payload.details.as_deref().map(str::trim).filter(|d| !d.is_empty()))
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating report: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    // Already reported by this user and still open
+    .ok_or(StatusCode::CONFLICT)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": { "id": id, "status": report_status::OPEN }
+    })))
+}
+
+// Oldest open reports first, so nothing waits forever
+async fn get_report_queue(
+    State(db): State<Database>,
+    Query(params): Query<ReportQuery>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_moderator(&db, &claims.sub).await?;
+
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+    let status = params.status.as_deref().unwrap_or(report_status::OPEN);
+
+    let reports = sqlx::query_as::<_, Report>(&format!(
+        "{} WHERE r.status = $1 AND ($2::text IS NULL OR r.target_type = $2)
+         ORDER BY r.created_at
+         LIMIT $3 OFFSET $4",
+        REPORT_SELECT
+    ))
+    .bind(status)
+    .bind(&params.target_type)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching reports: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM reports WHERE status = $1 AND ($2::text IS NULL OR target_type = $2)"
+    )
+    .bind(status)
+    .bind(&params.target_type)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": reports,
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": total,
+            "pages": ((total as f64) / (limit as f64)).ceil() as u32
+        }
+    })))
+}
+
+// Only open reports can be closed, so two moderators cannot both act on one
+async fn resolve_report(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<ResolveReportRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_moderator(&db, &claims.sub).await?;
+
+    if payload.status != report_status::RESOLVED && payload.status != report_status::DISMISSED {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let updated = sqlx::query_scalar::<_, Uuid>(
+        "UPDATE reports
+         SET status = $2, resolved_by = $3, resolution_note = $4, resolved_at = NOW()
+         WHERE id = $1 AND status = $5
+         RETURNING id"
+    )
+    .bind(id)
+    .bind(&payload.status)
+    .bind(&claims.sub)
+    .bind(&payload.resolution_note)
+    .bind(report_status::OPEN)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error resolving report: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if updated.is_none() {
+        let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM reports WHERE id = $1)")
+            .bind(id)
+            .fetch_one(&db.pool)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        return Err(if exists { StatusCode::CONFLICT } else { StatusCode::NOT_FOUND });
+    }
+
+    let report = sqlx::query_as::<_, Report>(&format!("{} WHERE r.id = $1", REPORT_SELECT))
+        .bind(id)
+        .fetch_one(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": report
+    })))
+}
+
+async fn ensure_moderator(db: &Database, user_id: &str) -> Result<(), StatusCode> {
+    if !db.is_moderator(user_id).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(())
+}
+
+/// Who is responsible for the reported item; 404 when it does not exist
+async fn target_owner(db: &Database, target_type: &str, target_id: &str) -> Result<String, StatusCode> {
+    let query = match target_type {
+        report_target::USER => {
+            return sqlx::query_scalar::<_, String>("SELECT id FROM users WHERE id = $1")
+                .bind(target_id)
+                .fetch_optional(&db.pool)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+                .ok_or(StatusCode::NOT_FOUND);
+        }
+        report_target::POST => "SELECT user_id FROM posts WHERE id = $1",
+        report_target::COMMENT => "SELECT user_id FROM comments WHERE id = $1",
+        report_target::PRODUCT => "SELECT user_id FROM products WHERE id = $1",
+        report_target::CAMPAIGN => "SELECT creator_id FROM campaigns WHERE id = $1",
+        _ => return Err(StatusCode::UNPROCESSABLE_ENTITY),
+    };
+
+    let id = Uuid::parse_str(target_id).map_err(|_| StatusCode::NOT_FOUND)?;
+
+    sqlx::query_scalar::<_, String>(query)
+        .bind(id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
@@ -0,0 +1,322 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, put},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::purchase_status,
+    routes::products::PRODUCT_CACHE_PREFIX,
+};
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ProductReview {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub user_id: String,
+    pub username: Option<String>,
+    pub avatar: Option<String>,
+    pub rating: i16,
+    pub body: Option<String>,
+    pub creator_reply: Option<String>,
+    pub creator_replied_at: Option<DateTime<Utc>>,
+    pub is_hidden: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewRequest {
+    pub rating: i16,
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewReplyRequest {
+    pub reply: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewVisibilityRequest {
+    pub hidden: bool,
+}
+
+const REVIEW_SELECT: &str = "SELECT r.id, r.product_id, r.user_id, u.username, u.avatar_url AS avatar, r.rating, r.body,
+        r.creator_reply, r.creator_replied_at, r.is_hidden, r.created_at, r.updated_at
+     FROM product_reviews r
+     LEFT JOIN users u ON u.id = r.user_id";
+
+pub fn review_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id/reviews", get(get_product_reviews).post(create_review))
+        .route("/:id/reviews/:review_id", put(update_review))
+        .route("/:id/reviews/:review_id/reply", put(reply_to_review))
+        .route("/:id/reviews/:review_id/visibility", put(set_review_visibility))
+}
+
+async fn get_product_reviews(
+    State(db): State<Database>,
+    Path(product_id): Path<Uuid>,
+    Query(params): Query<ReviewQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let reviews = sqlx::query_as::<_, ProductReview>(&format!(
+        "{} WHERE r.product_id = $1 AND r.is_hidden = false ORDER BY r.created_at DESC LIMIT $2 OFFSET $3",
+        REVIEW_SELECT
+    ))
+    .bind(product_id)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching reviews: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let summary = sqlx::query_as::<_, (Option<f64>, i32)>(
+        "SELECT rating_average, rating_count FROM products WHERE id = $1"
+    )
+    .bind(product_id)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    let response = serde_json::json!({
+        "success": true,
+        "data": reviews,
+        "summary": {
+            "average": summary.0,
+            "count": summary.1
+        },
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": summary.1,
+            "pages": ((summary.1 as f64) / (limit as f64)).ceil() as u32
+        }
+    });
+
+    Ok(Json(response))
+}
+
+async fn create_review(
+    State(db): State<Database>,
+    Path(product_id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<ReviewRequest>,
+) -> Result<Json<ProductReview>, StatusCode> {
+    if !(1..=5).contains(&payload.rating) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Only buyers with a completed purchase may review
+    let is_verified_buyer = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM purchases WHERE product_id = $1 AND user_id = $2 AND status = ANY($3))"
+    )
+    .bind(product_id)
+    .bind(&claims.sub)
+    .bind(purchase_status::SETTLED)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if !is_verified_buyer {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let review_id = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO product_reviews (product_id, user_id, rating, body)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (product_id, user_id) DO NOTHING
+         RETURNING id"
+    )
+    .bind(product_id)
+    .bind(&claims.sub)
+    .bind(payload.rating)
+    .bind(&payload.body)
+    .fetch_optional(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating review: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::CONFLICT)?;
+
+    refresh_product_rating(&mut tx, product_id).await?;
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+
+    fetch_review(&db, review_id).await.map(Json)
+}
+
+async fn update_review(
+    State(db): State<Database>,
+    Path((product_id, review_id)): Path<(Uuid, Uuid)>,
+    claims: Claims,
+    Json(payload): Json<ReviewRequest>,
+) -> Result<Json<ProductReview>, StatusCode> {
+    if !(1..=5).contains(&payload.rating) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Reviewers may only edit their own review
+    let review = fetch_review(&db, review_id).await?;
+    if review.product_id != product_id {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    if review.user_id != claims.sub {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let result = sqlx::query(
+        "UPDATE product_reviews SET rating = $4, body = $5, updated_at = NOW()
+         WHERE id = $1 AND product_id = $2 AND user_id = $3"
+    )
+    .bind(review_id)
+    .bind(product_id)
+    .bind(&claims.sub)
+    .bind(payload.rating)
+    .bind(&payload.body)
+    .execute(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    refresh_product_rating(&mut tx, product_id).await?;
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+
+    fetch_review(&db, review_id).await.map(Json)
+}
+
+async fn reply_to_review(
+    State(db): State<Database>,
+    Path((product_id, review_id)): Path<(Uuid, Uuid)>,
+    claims: Claims,
+    Json(payload): Json<ReviewReplyRequest>,
+) -> Result<Json<ProductReview>, StatusCode> {
+    ensure_product_owner(&db, product_id, &claims.sub).await?;
+
+    let result = sqlx::query(
+        "UPDATE product_reviews SET creator_reply = $3, creator_replied_at = NOW()
+         WHERE id = $1 AND product_id = $2"
+    )
+    .bind(review_id)
+    .bind(product_id)
+    .bind(&payload.reply)
+    .execute(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    fetch_review(&db, review_id).await.map(Json)
+}
+
+async fn set_review_visibility(
+    State(db): State<Database>,
+    Path((product_id, review_id)): Path<(Uuid, Uuid)>,
+    claims: Claims,
+    Json(payload): Json<ReviewVisibilityRequest>,
+) -> Result<Json<ProductReview>, StatusCode> {
+    ensure_product_owner(&db, product_id, &claims.sub).await?;
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let result = sqlx::query(
+        "UPDATE product_reviews SET is_hidden = $3, updated_at = NOW() WHERE id = $1 AND product_id = $2"
+    )
+    .bind(review_id)
+    .bind(product_id)
+    .bind(payload.hidden)
+    .execute(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    // Hidden reviews no longer count towards the product rating
+    refresh_product_rating(&mut tx, product_id).await?;
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+
+    fetch_review(&db, review_id).await.map(Json)
+}
+
+async fn ensure_product_owner(db: &Database, product_id: Uuid, user_id: &str) -> Result<(), StatusCode> {
+    let is_owner = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM products WHERE id = $1 AND user_id = $2)"
+    )
+    .bind(product_id)
+    .bind(user_id)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if is_owner {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+async fn fetch_review(db: &Database, review_id: Uuid) -> Result<ProductReview, StatusCode> {
+    sqlx::query_as::<_, ProductReview>(&format!("{} WHERE r.id = $1", REVIEW_SELECT))
+        .bind(review_id)
+        .fetch_one(&db.pool)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)
+}
+
+async fn refresh_product_rating(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    product_id: Uuid,
+) -> Result<(), StatusCode> {
+    sqlx::query(
+        "UPDATE products SET
+            rating_average = (SELECT AVG(rating)::float8 FROM product_reviews WHERE product_id = $1 AND is_hidden = false),
+            rating_count = (SELECT COUNT(*) FROM product_reviews WHERE product_id = $1 AND is_hidden = false)
+         WHERE id = $1"
+    )
+    .bind(product_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error refreshing product rating: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(())
+}
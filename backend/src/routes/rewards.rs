@@ -0,0 +1,544 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json},
+    routing::{get, put},
+    Router,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::{donation_status, fulfillment_status},
+    routes::campaigns::ensure_campaign_owner,
+};
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardTier {
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(rename = "amount")]
+    pub min_amount: f64,
+    #[serde(rename = "limitedQuantity")]
+    pub quantity_limit: Option<i32>,
+    pub quantity_claimed: i32,
+    pub remaining_quantity: Option<i32>,
+    pub estimated_delivery: Option<NaiveDate>,
+    #[serde(rename = "shippingIncluded")]
+    pub requires_shipping: bool,
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardTierRequest {
+    pub title: String,
+    pub description: Option<String>,
+    pub amount: f64,
+    pub limited_quantity: Option<i32>,
+    pub estimated_delivery: Option<NaiveDate>,
+    pub shipping_included: Option<bool>,
+    pub position: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShippingAddress {
+    pub name: String,
+    pub line1: String,
+    pub line2: Option<String>,
+    pub city: String,
+    pub region: Option<String>,
+    pub postal_code: String,
+    pub country: String,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Fulfillment {
+    pub id: Uuid,
+    pub donation_id: Uuid,
+    pub reward_tier_id: Uuid,
+    pub reward_title: String,
+    pub status: String,
+    pub backer_id: Option<String>,
+    pub backer_username: Option<String>,
+    pub backer_email: Option<String>,
+    pub amount: f64,
+    pub shipping_name: Option<String>,
+    pub address_line1: Option<String>,
+    pub address_line2: Option<String>,
+    pub city: Option<String>,
+    pub region: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: Option<String>,
+    pub tracking_number: Option<String>,
+    pub shipped_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FulfillmentQuery {
+    pub status: Option<String>,
+    pub reward_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFulfillmentRequest {
+    pub status: String,
+    pub tracking_number: Option<String>,
+}
+
+const REWARD_SELECT: &str = "SELECT id, campaign_id, title, description, min_amount, quantity_limit, quantity_claimed,
+        quantity_limit - quantity_claimed AS remaining_quantity, estimated_delivery, requires_shipping,
+        position, created_at, updated_at
+     FROM campaign_reward_tiers";
+
+// Fulfillments only matter once the backer has actually paid
+const FULFILLMENT_SELECT: &str = "SELECT f.id, f.donation_id, f.reward_tier_id, t.title AS reward_title, f.status,
+        d.user_id AS backer_id, u.username AS backer_username, u.email AS backer_email, d.amount,
+        f.shipping_name, f.address_line1, f.address_line2, f.city, f.region, f.postal_code, f.country,
+        f.tracking_number, f.shipped_at, f.created_at
+     FROM reward_fulfillments f
+     JOIN campaign_reward_tiers t ON t.id = f.reward_tier_id
+     JOIN donations d ON d.id = f.donation_id
+     LEFT JOIN users u ON u.id = d.user_id
+     WHERE t.campaign_id = $1 AND d.status = ANY($2)";
+
+/// Routes merged into `/api/campaigns`
+pub fn campaign_reward_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id/rewards", get(get_rewards).post(create_reward))
+        .route("/:id/rewards/:reward_id", put(update_reward).delete(delete_reward))
+        .route("/:id/fulfillment", get(get_fulfillment))
+        .route("/:id/fulfillment/export", get(export_fulfillment))
+        .route("/:id/fulfillment/:fulfillment_id", put(update_fulfillment))
+}
+
+pub(crate) async fn list_rewards(db: &Database, campaign_id: Uuid) -> Result<Vec<RewardTier>, sqlx::Error> {
+    sqlx::query_as::<_, RewardTier>(&format!(
+        "{} WHERE campaign_id = $1 ORDER BY position, min_amount",
+        REWARD_SELECT
+    ))
+    .bind(campaign_id)
+    .fetch_all(&db.pool)
+    .await
+}
+
+async fn get_rewards(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let rewards = list_rewards(&db, campaign_id).await.map_err(|e| {
+        eprintln!("Error fetching rewards: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": rewards
+    })))
+}
+
+async fn create_reward(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<RewardTierRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+    validate_reward(&payload)?;
+
+    let reward_id = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO campaign_reward_tiers
+            (campaign_id, title, description, min_amount, quantity_limit, estimated_delivery, requires_shipping, position)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         RETURNING id"
+    )
+    .bind(campaign_id)
+    .bind(payload.title.trim())
+    .bind(&payload.description)
+    .bind(payload.amount)
+    .bind(payload.limited_quantity)
+    .bind(payload.estimated_delivery)
+    .bind(payload.shipping_included.unwrap_or(false))
+    .bind(payload.position.unwrap_or(0))
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating reward: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let reward = fetch_reward(&db, campaign_id, reward_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": reward
+    })))
+}
+
+async fn update_reward(
+    State(db): State<Database>,
+    Path((campaign_id, reward_id)): Path<(Uuid, Uuid)>,
+    claims: Claims,
+    Json(payload): Json<RewardTierRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+    validate_reward(&payload)?;
+
+    // The limit may not drop below what backers have already claimed
+    let result = sqlx::query(
+        "UPDATE campaign_reward_tiers
+         SET title = $3, description = $4, min_amount = $5, quantity_limit = $6, estimated_delivery = $7,
+             requires_shipping = $8, position = COALESCE($9, position), updated_at = NOW()
+         WHERE id = $1 AND campaign_id = $2 AND ($6::int IS NULL OR $6 >= quantity_claimed)"
+    )
+    .bind(reward_id)
+    .bind(campaign_id)
+    .bind(payload.title.trim())
+    .bind(&payload.description)
+    .bind(payload.amount)
+    .bind(payload.limited_quantity)
+    .bind(payload.estimated_delivery)
+    .bind(payload.shipping_included.unwrap_or(false))
+    .bind(payload.position)
+    .execute(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error updating reward: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if result.rows_affected() == 0 {
+        // Either the tier does not exist or the new limit is too low
+        fetch_reward(&db, campaign_id, reward_id).await?;
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let reward = fetch_reward(&db, campaign_id, reward_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": reward
+    })))
+}
+
+async fn delete_reward(
+    State(db): State<Database>,
+    Path((campaign_id, reward_id)): Path<(Uuid, Uuid)>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+
+    // Tiers that backers have picked must stay so their pledges keep a reward
+    let result = sqlx::query(
+        "DELETE FROM campaign_reward_tiers WHERE id = $1 AND campaign_id = $2 AND quantity_claimed = 0"
+    )
+    .bind(reward_id)
+    .bind(campaign_id)
+    .execute(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        fetch_reward(&db, campaign_id, reward_id).await?;
+        return Err(StatusCode::CONFLICT);
+    }
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Reward deleted"
+    })))
+}
+
+async fn get_fulfillment(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+    claims: Claims,
+    Query(params): Query<FulfillmentQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+
+    let fulfillments = sqlx::query_as::<_, Fulfillment>(&format!(
+        "{} AND ($3::text IS NULL OR f.status = $3) AND ($4::uuid IS NULL OR f.reward_tier_id = $4)
+         ORDER BY f.created_at",
+        FULFILLMENT_SELECT
+    ))
+    .bind(campaign_id)
+    .bind(donation_status::SETTLED)
+    .bind(&params.status)
+    .bind(params.reward_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching fulfillment: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let summary = sqlx::query_as::<_, (String, i64)>(
+        "SELECT f.status, COUNT(*)
+         FROM reward_fulfillments f
+         JOIN campaign_reward_tiers t ON t.id = f.reward_tier_id
+         JOIN donations d ON d.id = f.donation_id
+         WHERE t.campaign_id = $1 AND d.status = ANY($2)
+         GROUP BY f.status"
+    )
+    .bind(campaign_id)
+    .bind(donation_status::SETTLED)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let summary: serde_json::Map<String, serde_json::Value> = summary
+        .into_iter()
+        .map(|(status, count)| (status, serde_json::json!(count)))
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": fulfillments,
+        "summary": summary
+    })))
+}
+
+async fn export_fulfillment(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+    claims: Claims,
+) -> Result<impl IntoResponse, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+
+    // Shipping labels are only needed for physical rewards that have not gone out yet
+    let fulfillments = sqlx::query_as::<_, Fulfillment>(&format!(
+        "{} AND t.requires_shipping = true AND f.status = $3 ORDER BY t.position, f.created_at",
+        FULFILLMENT_SELECT
+    ))
+    .bind(campaign_id)
+    .bind(donation_status::SETTLED)
+    .bind(fulfillment_status::PENDING)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut csv = String::from(
+        "fulfillment_id,reward,backer_email,name,address_line1,address_line2,city,region,postal_code,country\n",
+    );
+    for f in &fulfillments {
+        let row = [
+            f.id.to_string(),
+            f.reward_title.clone(),
+            f.backer_email.clone().unwrap_or_default(),
+            f.shipping_name.clone().unwrap_or_default(),
+            f.address_line1.clone().unwrap_or_default(),
+            f.address_line2.clone().unwrap_or_default(),
+            f.city.clone().unwrap_or_default(),
+            f.region.clone().unwrap_or_default(),
+            f.postal_code.clone().unwrap_or_default(),
+            f.country.clone().unwrap_or_default(),
+        ];
+        let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    let disposition = format!("attachment; filename=\"campaign-{}-shipping.csv\"", campaign_id);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        csv,
+    ))
+}
+
+async fn update_fulfillment(
+    State(db): State<Database>,
+    Path((campaign_id, fulfillment_id)): Path<(Uuid, Uuid)>,
+    claims: Claims,
+    Json(payload): Json<UpdateFulfillmentRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+
+    let current = sqlx::query_scalar::<_, String>(
+        "SELECT f.status FROM reward_fulfillments f
+         JOIN campaign_reward_tiers t ON t.id = f.reward_tier_id
+         WHERE f.id = $1 AND t.campaign_id = $2"
+    )
+    .bind(fulfillment_id)
+    .bind(campaign_id)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    if !fulfillment_status::can_transition(&current, &payload.status) {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    // Compare-and-set on the status we validated against
+    let result = sqlx::query(
+        "UPDATE reward_fulfillments
+         SET status = $3, tracking_number = COALESCE($4, tracking_number),
+             shipped_at = CASE WHEN $3 = $5 THEN NOW() ELSE shipped_at END, updated_at = NOW()
+         WHERE id = $1 AND status = $2"
+    )
+    .bind(fulfillment_id)
+    .bind(&current)
+    .bind(&payload.status)
+    .bind(&payload.tracking_number)
+    .bind(fulfillment_status::SHIPPED)
+    .execute(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            "id": fulfillment_id,
+            "status": payload.status,
+            "trackingNumber": payload.tracking_number
+        }
+    })))
+}
+
+/// Claim one unit of a reward tier for a new pledge. The conditional update
+/// makes the quantity check and decrement a single atomic step, so concurrent
+/// backers cannot oversell a limited tier. Returns whether the tier ships.
+pub(crate) async fn claim_reward(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    campaign_id: Uuid,
+    reward_id: Uuid,
+    amount: f64,
+) -> Result<bool, StatusCode> {
+    let tier = sqlx::query_as::<_, (f64, bool)>(
+        "SELECT min_amount, requires_shipping FROM campaign_reward_tiers WHERE id = $1 AND campaign_id = $2"
+    )
+    .bind(reward_id)
+    .bind(campaign_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    if amount < tier.0 {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let claimed = sqlx::query(
+        "UPDATE campaign_reward_tiers SET quantity_claimed = quantity_claimed + 1, updated_at = NOW()
+         WHERE id = $1 AND (quantity_limit IS NULL OR quantity_claimed < quantity_limit)"
+    )
+    .bind(reward_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Sold out
+    if claimed.rows_affected() == 0 {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    Ok(tier.1)
+}
+
+pub(crate) async fn create_fulfillment(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    donation_id: Uuid,
+    reward_id: Uuid,
+    address: Option<&ShippingAddress>,
+) -> Result<(), StatusCode> {
+    sqlx::query(
+        "INSERT INTO reward_fulfillments
+            (donation_id, reward_tier_id, status, shipping_name, address_line1, address_line2, city, region, postal_code, country)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, UPPER($10))"
+    )
+    .bind(donation_id)
+    .bind(reward_id)
+    .bind(fulfillment_status::PENDING)
+    .bind(address.map(|a| a.name.as_str()))
+    .bind(address.map(|a| a.line1.as_str()))
+    .bind(address.and_then(|a| a.line2.as_deref()))
+    .bind(address.map(|a| a.city.as_str()))
+    .bind(address.and_then(|a| a.region.as_deref()))
+    .bind(address.map(|a| a.postal_code.as_str()))
+    .bind(address.map(|a| a.country.as_str()))
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating reward fulfillment: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(())
+}
+
+/// Give a claimed reward back to its tier when the pledge behind it fails or
+/// is refunded in full. Rewards that have already shipped are left alone.
+pub(crate) async fn release_reward(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    donation_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "WITH cancelled AS (
+             UPDATE reward_fulfillments SET status = $3, updated_at = NOW()
+             WHERE donation_id = $1 AND status = $2
+             RETURNING reward_tier_id
+         )
+         UPDATE campaign_reward_tiers
+         SET quantity_claimed = GREATEST(quantity_claimed - 1, 0), updated_at = NOW()
+         WHERE id IN (SELECT reward_tier_id FROM cancelled)"
+    )
+    .bind(donation_id)
+    .bind(fulfillment_status::PENDING)
+    .bind(fulfillment_status::CANCELLED)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+fn validate_reward(payload: &RewardTierRequest) -> Result<(), StatusCode> {
+    let valid = !payload.title.trim().is_empty()
+        && payload.amount.is_finite()
+        && payload.amount >= 1.0
+        && payload.limited_quantity.is_none_or(|limit| limit > 0);
+
+    if valid {
+        Ok(())
+    } else {
+        Err(StatusCode::UNPROCESSABLE_ENTITY)
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+async fn fetch_reward(db: &Database, campaign_id: Uuid, reward_id: Uuid) -> Result<RewardTier, StatusCode> {
+    sqlx::query_as::<_, RewardTier>(&format!("{} WHERE id = $1 AND campaign_id = $2", REWARD_SELECT))
+        .bind(reward_id)
+        .bind(campaign_id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
@@ -0,0 +1,260 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::{campaign_status, post_status, purchase_status, subscription_status},
+    routes::{blocks::hidden_users, posts::POST_SELECT},
+};
+
+const DEFAULT_SEARCH_LIMIT: i64 = 5;
+const MAX_SEARCH_LIMIT: i64 = 20;
+const MAX_QUERY_LENGTH: usize = 200;
+
+// ts_headline returns the source text as is, so matches are delimited with
+// private-use characters and only turned into <mark> after escaping the rest
+const MATCH_START: char = '\u{E000}';
+const MATCH_STOP: char = '\u{E001}';
+const HEADLINE_OPTIONS: &str = "StartSel=\u{E000}, StopSel=\u{E001}, MaxWords=30, MinWords=10, MaxFragments=2";
+
+/// One search result, whatever its type
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub id: String,
+    pub title: String,
+    /// Username for creators, slug for articles and campaigns
+    pub slug: Option<String>,
+    pub snippet: Option<String>,
+    pub creator_id: Option<String>,
+    pub rank: f32,
+    /// Premium post the viewer cannot read; its snippet comes from the preview
+    pub locked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    /// Restrict to one group: creators, posts, articles, products or campaigns
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub limit: Option<i64>,
+}
+
+pub fn search_routes() -> Router<Database> {
+    Router::new()
+        .route("/", get(search))
+}
+
+async fn search(
+    State(db): State<Database>,
+    Query(params): Query<SearchQuery>,
+    claims: Option<Claims>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let q = params.q.trim();
+    if q.is_empty() || q.chars().count() > MAX_QUERY_LENGTH {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let kind = params.kind.as_deref();
+    if kind.is_some_and(|k| !["creators", "posts", "articles", "products", "campaigns"].contains(&k)) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let limit = params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, MAX_SEARCH_LIMIT);
+    let wants = |group: &str| kind.is_none_or(|k| k == group);
+    let viewer = claims.as_ref().map(|c| c.sub.as_str());
+
+    let mut results = serde_json::Map::new();
+
+    if wants("creators") {
+        // Trigram similarity on the username catches typos full-text search misses
+        let creators = sqlx::query_as::<_, SearchHit>(&format!(
+            "SELECT u.id, COALESCE(u.display_name, u.username) AS title, u.username AS slug,
+                    ts_headline('english', COALESCE(u.bio, ''), websearch_to_tsquery('english', $1), '{}') AS snippet,
+                    u.id AS creator_id,
+                    GREATEST(ts_rank(u.search_vector, websearch_to_tsquery('english', $1)),
+                             similarity(COALESCE(u.username, ''), $1)) AS rank,
+                    FALSE AS locked
+             FROM users u
+             WHERE u.is_creator = TRUE AND u.id NOT IN {}
+               AND (u.search_vector @@ websearch_to_tsquery('english', $1) OR u.username % $1)
+             ORDER BY rank DESC
+             LIMIT $2",
+            HEADLINE_OPTIONS, hidden_users("$3")
+        ))
+        .bind(q)
+        .bind(limit)
+        .bind(viewer)
+        .fetch_all(&db.pool)
+        .await
+        .map_err(search_error)?;
+        results.insert("creators".into(), serde_json::json!(highlighted(creators)));
+    }
+
+    if wants("posts") {
+        // Only published posts; locked ones are matched and excerpted from their
+        // title and preview only, since premium bodies are not indexed
+        let posts = sqlx::query_as::<_, SearchHit>(&format!(
+            "SELECT p.id::text AS id, p.title, NULL AS slug,
+                    ts_headline('english',
+                        CASE WHEN p.locked THEN COALESCE(p.preview, '') ELSE COALESCE(p.content, '') END,
+                        websearch_to_tsquery('english', $5), '{}') AS snippet,
+                    p.user_id AS creator_id,
+                    ts_rank(p.search_vector, websearch_to_tsquery('english', $5)) AS rank,
+                    p.locked
+             FROM ({}) p
+             WHERE p.status = $4 AND p.search_vector @@ websearch_to_tsquery('english', $5) AND p.user_id NOT IN {}
+             ORDER BY rank DESC
+             LIMIT $6",
+            HEADLINE_OPTIONS, POST_SELECT, hidden_users("$1")
+        ))
+        .bind(viewer)
+        .bind(subscription_status::ENTITLED)
+        .bind(purchase_status::SETTLED)
+        .bind(post_status::PUBLISHED)
+        .bind(q)
+        .bind(limit)
+        .fetch_all(&db.pool)
+        .await
+        .map_err(search_error)?;
+        results.insert("posts".into(), serde_json::json!(highlighted(posts)));
+    }
+
+    // Articles are created outside the migrations
+    if wants("articles") && db.table_exists("articles").await.map_err(search_error)? {
+        let articles = sqlx::query_as::<_, SearchHit>(&format!(
+            "SELECT a.id::text AS id, a.title, a.slug,
+                    ts_headline('english', COALESCE(a.content, ''), websearch_to_tsquery('english', $1), '{}') AS snippet,
+                    a.author_id AS creator_id,
+                    ts_rank(a.search_vector, websearch_to_tsquery('english', $1)) AS rank,
+                    FALSE AS locked
+             FROM articles a
+             WHERE a.published_at <= NOW() AND a.search_vector @@ websearch_to_tsquery('english', $1)
+               AND a.author_id NOT IN {}
+             ORDER BY rank DESC
+             LIMIT $2",
+            HEADLINE_OPTIONS, hidden_users("$3")
+        ))
+        .bind(q)
+        .bind(limit)
+        .bind(viewer)
+        .fetch_all(&db.pool)
+        .await
+        .map_err(search_error)?;
+        results.insert("articles".into(), serde_json::json!(highlighted(articles)));
+    }
+
+    if wants("products") {
+        let products = sqlx::query_as::<_, SearchHit>(&format!(
+            "SELECT pr.id::text AS id, pr.name AS title, NULL AS slug,
+                    ts_headline('english', COALESCE(pr.description, ''), websearch_to_tsquery('english', $1), '{}') AS snippet,
+                    pr.user_id AS creator_id,
+                    ts_rank(pr.search_vector, websearch_to_tsquery('english', $1)) AS rank,
+                    FALSE AS locked
+             FROM products pr
+             WHERE pr.search_vector @@ websearch_to_tsquery('english', $1) AND pr.user_id NOT IN {}
+             ORDER BY rank DESC
+             LIMIT $2",
+            HEADLINE_OPTIONS, hidden_users("$3")
+        ))
+        .bind(q)
+        .bind(limit)
+        .bind(viewer)
+        .fetch_all(&db.pool)
+        .await
+        .map_err(search_error)?;
+        results.insert("products".into(), serde_json::json!(highlighted(products)));
+    }
+
+    if wants("campaigns") {
+        // Drafts and cancelled campaigns stay out of search, as in the listing
+        let campaigns = sqlx::query_as::<_, SearchHit>(&format!(
+            "SELECT c.id::text AS id, c.title, c.slug,
+                    ts_headline('english', COALESCE(c.description, ''), websearch_to_tsquery('english', $1), '{}') AS snippet,
+                    c.creator_id,
+                    ts_rank(c.search_vector, websearch_to_tsquery('english', $1)) AS rank,
+                    FALSE AS locked
+             FROM campaigns c
+             WHERE c.status = ANY($2) AND c.search_vector @@ websearch_to_tsquery('english', $1)
+               AND c.creator_id NOT IN {}
+             ORDER BY rank DESC
+             LIMIT $3",
+            HEADLINE_OPTIONS, hidden_users("$4")
+        ))
+        .bind(q)
+        .bind(&[campaign_status::ACTIVE, campaign_status::FUNDED, campaign_status::ENDED][..])
+        .bind(limit)
+        .bind(viewer)
+        .fetch_all(&db.pool)
+        .await
+        .map_err(search_error)?;
+        results.insert("campaigns".into(), serde_json::json!(highlighted(campaigns)));
+    }
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": results
+    })))
+}
+
+/// HTML-escapes each snippet and wraps its matches in `<mark>`
+fn highlighted(mut hits: Vec<SearchHit>) -> Vec<SearchHit> {
+    for hit in &mut hits {
+        hit.snippet = hit.snippet.as_deref().map(highlight_snippet);
+    }
+    hits
+}
+
+fn highlight_snippet(raw: &str) -> String {
+    let mut html = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            MATCH_START => html.push_str("<mark>"),
+            MATCH_STOP => html.push_str("</mark>"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+fn search_error(e: sqlx::Error) -> StatusCode {
+    eprintln!("Error searching: {:?}", e);
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlight_snippet_escapes_source_text() {
+        assert_eq!(
+            highlight_snippet("<img src=x onerror=\"alert('x')\"> & more"),
+            "&lt;img src=x onerror=&quot;alert(&#39;x&#39;)&quot;&gt; &amp; more"
+        );
+    }
+
+    #[test]
+    fn highlight_snippet_marks_matches() {
+        let raw = format!("learn {}rust{} <b>today</b>", MATCH_START, MATCH_STOP);
+        assert_eq!(highlight_snippet(&raw), "learn <mark>rust</mark> &lt;b&gt;today&lt;/b&gt;");
+    }
+
+    #[test]
+    fn highlight_snippet_keeps_literal_mark_tags_escaped() {
+        assert_eq!(highlight_snippet("<mark>x</mark>"), "&lt;mark&gt;x&lt;/mark&gt;");
+    }
+}
@@ -0,0 +1,106 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::database::Database;
+
+// Values stored in `stream_events.kind`
+pub mod event_kind {
+    pub const STRETCH_GOAL_UNLOCKED: &str = "stretch_goal_unlocked";
+    pub const GOAL_REACHED: &str = "goal_reached";
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamEvent {
+    pub id: Uuid,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub title: String,
+    pub message: Option<String>,
+    pub amount: Option<f64>,
+    pub campaign_id: Option<Uuid>,
+    #[serde(rename = "timestamp")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamEventQuery {
+    pub creator_id: String,
+    /// Only events newer than this, so widgets can poll for what they missed
+    pub since: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+}
+
+/// A new entry for a creator's stream widgets
+pub(crate) struct NewStreamEvent<'a> {
+    pub creator_id: &'a str,
+    pub kind: &'a str,
+    pub title: String,
+    pub message: Option<String>,
+    pub amount: Option<f64>,
+    pub campaign_id: Option<Uuid>,
+}
+
+pub fn stream_routes() -> Router<Database> {
+    Router::new()
+        .route("/events", get(get_stream_events))
+}
+
+// Public so OBS browser sources can poll it without a token
+async fn get_stream_events(
+    State(db): State<Database>,
+    Query(params): Query<StreamEventQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let limit = params.limit.unwrap_or(10).clamp(1, 50);
+
+    let events = sqlx::query_as::<_, StreamEvent>(
+        "SELECT id, kind, title, message, amount, campaign_id, created_at
+         FROM stream_events
+         WHERE creator_id = $1 AND ($2::timestamptz IS NULL OR created_at > $2)
+         ORDER BY created_at DESC
+         LIMIT $3"
+    )
+    .bind(&params.creator_id)
+    .bind(params.since)
+    .bind(limit as i64)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching stream events: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": events
+    })))
+}
+
+pub(crate) async fn record_stream_event(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    event: NewStreamEvent<'_>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO stream_events (creator_id, kind, title, message, amount, campaign_id)
+         VALUES ($1, $2, $3, $4, $5, $6)"
+    )
+    .bind(event.creator_id)
+    .bind(event.kind)
+    .bind(&event.title)
+    .bind(&event.message)
+    .bind(event.amount)
+    .bind(event.campaign_id)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
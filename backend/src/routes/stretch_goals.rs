@@ -0,0 +1,275 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, put},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    notifications::{self, kind, NewNotification},
+    routes::{
+        campaigns::ensure_campaign_owner,
+        stream::{event_kind, record_stream_event, NewStreamEvent},
+    },
+};
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct StretchGoal {
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub target_amount: f64,
+    pub unlocked: bool,
+    pub unlocked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StretchGoalRequest {
+    pub title: String,
+    pub description: Option<String>,
+    pub target_amount: f64,
+}
+
+const STRETCH_GOAL_SELECT: &str = "SELECT id, campaign_id, title, description, target_amount,
+        unlocked_at IS NOT NULL AS unlocked, unlocked_at, created_at, updated_at
+     FROM campaign_stretch_goals";
+
+/// Routes merged into `/api/campaigns`
+pub fn campaign_stretch_goal_routes() -> Router<Database> {
+    Router::new()
+        .route("/:id/stretch-goals", get(get_stretch_goals).post(create_stretch_goal))
+        .route("/:id/stretch-goals/:goal_id", put(update_stretch_goal).delete(delete_stretch_goal))
+}
+
+/// Stretch goals in the order they unlock
+pub(crate) async fn list_stretch_goals(db: &Database, campaign_id: Uuid) -> Result<Vec<StretchGoal>, sqlx::Error> {
+    sqlx::query_as::<_, StretchGoal>(&format!(
+        "{} WHERE campaign_id = $1 ORDER BY target_amount",
+        STRETCH_GOAL_SELECT
+    ))
+    .bind(campaign_id)
+    .fetch_all(&db.pool)
+    .await
+}
+
+async fn get_stretch_goals(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let goals = list_stretch_goals(&db, campaign_id).await.map_err(|e| {
+        eprintln!("Error fetching stretch goals: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": goals
+    })))
+}
+
+async fn create_stretch_goal(
+    State(db): State<Database>,
+    Path(campaign_id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<StretchGoalRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+    validate_stretch_goal(&db, campaign_id, &payload).await?;
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let goal_id = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO campaign_stretch_goals (campaign_id, title, description, target_amount)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (campaign_id, target_amount) DO NOTHING
+         RETURNING id"
+    )
+    .bind(campaign_id)
+    .bind(payload.title.trim())
+    .bind(&payload.description)
+    .bind(payload.target_amount)
+    .fetch_optional(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating stretch goal: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::CONFLICT)?;
+
+    // A goal added below the current total unlocks straight away
+    unlock_stretch_goals(&mut tx, campaign_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let goal = fetch_stretch_goal(&db, campaign_id, goal_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": goal
+    })))
+}
+
+async fn update_stretch_goal(
+    State(db): State<Database>,
+    Path((campaign_id, goal_id)): Path<(Uuid, Uuid)>,
+    claims: Claims,
+    Json(payload): Json<StretchGoalRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+    validate_stretch_goal(&db, campaign_id, &payload).await?;
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Unlocked goals are a promise to backers and can no longer be moved
+    let result = sqlx::query(
+        "UPDATE campaign_stretch_goals SET title = $3, description = $4, target_amount = $5, updated_at = NOW()
+         WHERE id = $1 AND campaign_id = $2 AND unlocked_at IS NULL"
+    )
+    .bind(goal_id)
+    .bind(campaign_id)
+    .bind(payload.title.trim())
+    .bind(&payload.description)
+    .bind(payload.target_amount)
+    .execute(&mut tx)
+    .await
+    .map_err(|e| {
+        // Another goal already sits at this amount
+        let is_duplicate = e
+            .as_database_error()
+            .and_then(|db_error| db_error.code())
+            .is_some_and(|code| code == "23505");
+        if is_duplicate {
+            StatusCode::CONFLICT
+        } else {
+            eprintln!("Error updating stretch goal: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
+
+    if result.rows_affected() == 0 {
+        fetch_stretch_goal(&db, campaign_id, goal_id).await?;
+        return Err(StatusCode::CONFLICT);
+    }
+
+    unlock_stretch_goals(&mut tx, campaign_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let goal = fetch_stretch_goal(&db, campaign_id, goal_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": goal
+    })))
+}
+
+async fn delete_stretch_goal(
+    State(db): State<Database>,
+    Path((campaign_id, goal_id)): Path<(Uuid, Uuid)>,
+    claims: Claims,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_campaign_owner(&db, campaign_id, &claims.sub).await?;
+
+    let result = sqlx::query(
+        "DELETE FROM campaign_stretch_goals WHERE id = $1 AND campaign_id = $2 AND unlocked_at IS NULL"
+    )
+    .bind(goal_id)
+    .bind(campaign_id)
+    .execute(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        fetch_stretch_goal(&db, campaign_id, goal_id).await?;
+        return Err(StatusCode::CONFLICT);
+    }
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Stretch goal deleted"
+    })))
+}
+
+/// Unlock every stretch goal the campaign total has reached. Call in the same
+/// transaction that raises `current_amount`. Goals stay unlocked even if a
+/// later refund drops the total back below the threshold.
+pub(crate) async fn unlock_stretch_goals(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    campaign_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    let unlocked = sqlx::query_as::<_, (String, f64, String, String, String)>(
+        "UPDATE campaign_stretch_goals g
+         SET unlocked_at = NOW(), updated_at = NOW()
+         FROM campaigns c
+         WHERE g.campaign_id = c.id AND c.id = $1 AND g.unlocked_at IS NULL
+           AND COALESCE(c.current_amount, 0) >= g.target_amount
+         RETURNING g.title, g.target_amount, c.title, c.slug, c.creator_id"
+    )
+    .bind(campaign_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for (goal_title, target_amount, campaign_title, slug, creator_id) in &unlocked {
+        let notification = NewNotification {
+            kind: kind::STRETCH_GOAL_UNLOCKED,
+            title: format!("{} unlocked a stretch goal: {}", campaign_title, goal_title),
+            message: None,
+            link: Some(format!("/campaigns/{}", slug)),
+            actor_id: None,
+        };
+        notifications::notify_campaign_backers(tx, campaign_id, &notification).await?;
+        notifications::notify_user(tx, creator_id, &notification).await?;
+
+        record_stream_event(tx, NewStreamEvent {
+            creator_id,
+            kind: event_kind::STRETCH_GOAL_UNLOCKED,
+            title: format!("Stretch goal unlocked: {}", goal_title),
+            message: Some(campaign_title.clone()),
+            amount: Some(*target_amount),
+            campaign_id: Some(campaign_id),
+        })
+        .await?;
+    }
+
+    Ok(())
+}
+
+// Stretch goals sit above the main goal
+async fn validate_stretch_goal(db: &Database, campaign_id: Uuid, payload: &StretchGoalRequest) -> Result<(), StatusCode> {
+    let goal_amount = sqlx::query_scalar::<_, f64>("SELECT goal_amount FROM campaigns WHERE id = $1")
+        .bind(campaign_id)
+        .fetch_one(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if payload.title.trim().is_empty() || !payload.target_amount.is_finite() || payload.target_amount <= goal_amount {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    Ok(())
+}
+
+async fn fetch_stretch_goal(db: &Database, campaign_id: Uuid, goal_id: Uuid) -> Result<StretchGoal, StatusCode> {
+    sqlx::query_as::<_, StretchGoal>(&format!("{} WHERE id = $1 AND campaign_id = $2", STRETCH_GOAL_SELECT))
+        .bind(goal_id)
+        .bind(campaign_id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
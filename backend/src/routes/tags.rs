@@ -0,0 +1,333 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    database::Database,
+    models::{content_type, post_status, purchase_status, subscription_status, Post, Product},
+    routes::{
+        articles::Article,
+        blocks::hidden_users,
+        campaigns::slugify,
+        post_attachments::with_attachments,
+        posts::{redact_locked, POST_SELECT},
+    },
+};
+
+const MAX_TAG_LENGTH: usize = 50;
+const MAX_TAGS_PER_ITEM: usize = 10;
+const AUTOCOMPLETE_LIMIT: i64 = 10;
+
+/// A tag is global (a site-wide category, `creator_id` unset) or belongs to
+/// one creator. Slugs are unique within each scope.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Tag {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub creator_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// How many items carry the tag; only selected by autocomplete
+    #[sqlx(default)]
+    pub usage_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TagRequest {
+    pub name: String,
+    /// Admins only
+    pub global: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagQuery {
+    pub q: Option<String>,
+    pub creator_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TagPageQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+/// SQL condition for list filters: true when the slug parameter is null or the
+/// row identified by `id_column` carries a tag with that slug
+pub(crate) fn tag_filter(kind: &str, id_column: &str, slug_param: &str) -> String {
+    format!(
+        "({slug}::text IS NULL OR EXISTS(
+            SELECT 1 FROM content_tags ct JOIN tags tg ON tg.id = ct.tag_id
+            WHERE ct.content_type = '{kind}' AND ct.content_id = {id}::text AND tg.slug = {slug}))",
+        slug = slug_param,
+        kind = kind,
+        id = id_column,
+    )
+}
+
+/// Routes mounted under `/api/tags`
+pub fn tag_routes() -> Router<Database> {
+    Router::new()
+        .route("/", get(autocomplete_tags).post(create_tag))
+        .route("/:slug", get(get_tag_page))
+}
+
+// Global tags plus the given creator's, most used first
+async fn autocomplete_tags(
+    State(db): State<Database>,
+    Query(params): Query<TagQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let prefix = slugify(params.q.as_deref().unwrap_or(""));
+
+    let tags = sqlx::query_as::<_, Tag>(
+        "SELECT t.id, t.name, t.slug, t.creator_id, t.created_at, COUNT(ct.tag_id) AS usage_count
+         FROM tags t
+         LEFT JOIN content_tags ct ON ct.tag_id = t.id
+         WHERE t.slug LIKE $1 || '%' AND (t.creator_id IS NULL OR t.creator_id = $2)
+         GROUP BY t.id
+         ORDER BY usage_count DESC, t.slug
+         LIMIT $3"
+    )
+    .bind(&prefix)
+    .bind(&params.creator_id)
+    .bind(AUTOCOMPLETE_LIMIT)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching tags: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": tags
+    })))
+}
+
+async fn create_tag(
+    State(db): State<Database>,
+    claims: Claims,
+    Json(payload): Json<TagRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let global = payload.global.unwrap_or(false);
+    let allowed = if global {
+        db.is_admin(&claims.sub).await
+    } else {
+        db.is_creator(&claims.sub).await
+    }
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if !allowed {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let creator_id = (!global).then_some(claims.sub.as_str());
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let tag_id = resolve_tag(&mut tx, creator_id, &payload.name).await?;
+    let tag = sqlx::query_as::<_, Tag>("SELECT id, name, slug, creator_id, created_at FROM tags WHERE id = $1")
+        .bind(tag_id)
+        .fetch_one(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": tag
+    })))
+}
+
+// Public posts, articles and products carrying the tag, across all creators
+// except those the viewer blocked or muted or who blocked them
+async fn get_tag_page(
+    State(db): State<Database>,
+    Path(slug): Path<String>,
+    Query(params): Query<TagPageQuery>,
+    claims: Option<Claims>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let name = sqlx::query_scalar::<_, String>(
+        "SELECT name FROM tags WHERE slug = $1 ORDER BY creator_id NULLS FIRST LIMIT 1"
+    )
+    .bind(&slug)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    let posts = sqlx::query_as::<_, Post>(&format!(
+        "{} WHERE p.status = $4 AND {} AND p.user_id NOT IN {} ORDER BY p.published_at DESC LIMIT $6 OFFSET $7",
+        POST_SELECT,
+        tag_filter(content_type::POST, "p.id", "$5"),
+        hidden_users("$1")
+    ))
+    .bind(claims.as_ref().map(|c| c.sub.as_str()))
+    .bind(subscription_status::ENTITLED)
+    .bind(purchase_status::SETTLED)
+    .bind(post_status::PUBLISHED)
+    .bind(&slug)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching tagged posts: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let posts = with_attachments(&db, posts.into_iter().map(redact_locked).collect()).await?;
+
+    // Articles are created outside the migrations
+    let articles = if db.table_exists("articles").await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? {
+        sqlx::query_as::<_, Article>(&format!(
+            "SELECT * FROM articles a WHERE a.published_at <= NOW() AND {} AND a.author_id NOT IN {}
+             ORDER BY a.published_at DESC LIMIT $2 OFFSET $3",
+            tag_filter(content_type::ARTICLE, "a.id", "$1"),
+            hidden_users("$4")
+        ))
+        .bind(&slug)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .bind(claims.as_ref().map(|c| c.sub.as_str()))
+        .fetch_all(&db.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("Error fetching tagged articles: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+    } else {
+        Vec::new()
+    };
+
+    let products = sqlx::query_as::<_, Product>(&format!(
+        "SELECT * FROM products pr WHERE {} AND pr.user_id NOT IN {} ORDER BY pr.created_at DESC LIMIT $2 OFFSET $3",
+        tag_filter(content_type::PRODUCT, "pr.id", "$1"),
+        hidden_users("$4")
+    ))
+    .bind(&slug)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .bind(claims.as_ref().map(|c| c.sub.as_str()))
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching tagged products: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            "tag": { "name": name, "slug": slug },
+            "posts": posts,
+            "articles": articles,
+            "products": products
+        }
+    })))
+}
+
+/// Replace the tags on an item with `names`. Each name resolves to the
+/// owner's tag with that slug, then a global one, and is otherwise created
+/// for the owner.
+pub(crate) async fn set_content_tags(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    owner_id: &str,
+    kind: &str,
+    content_id: &str,
+    names: &[String],
+) -> Result<(), StatusCode> {
+    if names.len() > MAX_TAGS_PER_ITEM {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    sqlx::query("DELETE FROM content_tags WHERE content_type = $1 AND content_id = $2")
+        .bind(kind)
+        .bind(content_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    for name in names {
+        let tag_id = resolve_tag(tx, Some(owner_id), name).await?;
+        sqlx::query(
+            "INSERT INTO content_tags (tag_id, content_type, content_id) VALUES ($1, $2, $3)
+             ON CONFLICT DO NOTHING"
+        )
+        .bind(tag_id)
+        .bind(kind)
+        .bind(content_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    Ok(())
+}
+
+async fn resolve_tag(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    creator_id: Option<&str>,
+    name: &str,
+) -> Result<Uuid, StatusCode> {
+    let name = name.trim();
+    let slug = slugify(name);
+    if slug.is_empty() || name.chars().count() > MAX_TAG_LENGTH {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let existing = sqlx::query_scalar::<_, Uuid>(
+        "SELECT id FROM tags
+         WHERE slug = $1 AND (creator_id = $2 OR creator_id IS NULL)
+         ORDER BY creator_id NULLS LAST
+         LIMIT 1"
+    )
+    .bind(&slug)
+    .bind(creator_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+
+    // A concurrent insert of the same tag wins; pick up its row instead
+    let inserted = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO tags (name, slug, creator_id) VALUES ($1, $2, $3)
+         ON CONFLICT DO NOTHING
+         RETURNING id"
+    )
+    .bind(name)
+    .bind(&slug)
+    .bind(creator_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating tag: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    match inserted {
+        Some(id) => Ok(id),
+        None => sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM tags WHERE slug = $1 AND creator_id IS NOT DISTINCT FROM $2"
+        )
+        .bind(&slug)
+        .bind(creator_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post, put},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{auth::Claims, database::Database};
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct MembershipTier {
+    pub id: Uuid,
+    pub creator_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: f64,
+    pub currency: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MembershipTierRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub price: f64,
+    pub currency: Option<String>,
+}
+
+const TIER_SELECT: &str = "SELECT id, creator_id, name, description, price, currency, created_at, updated_at
+     FROM membership_tiers";
+
+/// Routes mounted under `/api/tiers`
+pub fn tier_routes() -> Router<Database> {
+    Router::new()
+        .route("/", post(create_tier))
+        .route("/:id", put(update_tier))
+}
+
+/// Routes merged into `/api/creators`
+pub fn creator_tier_routes() -> Router<Database> {
+    Router::new()
+        .route("/:username/tiers", get(get_creator_tiers))
+}
+
+// Cheapest first; a tier includes everything below it
+async fn get_creator_tiers(
+    State(db): State<Database>,
+    Path(username): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let tiers = sqlx::query_as::<_, MembershipTier>(&format!(
+        "{} WHERE creator_id = (SELECT id FROM users WHERE username = $1) ORDER BY price",
+        TIER_SELECT
+    ))
+    .bind(&username)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error fetching tiers: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": tiers
+    })))
+}
+
+async fn create_tier(
+    State(db): State<Database>,
+    claims: Claims,
+    Json(payload): Json<MembershipTierRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if !db.is_creator(&claims.sub).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    validate_tier(&payload)?;
+
+    let tier = sqlx::query_as::<_, MembershipTier>(
+        "INSERT INTO membership_tiers (creator_id, name, description, price, currency)
+         VALUES ($1, $2, $3, $4, UPPER(COALESCE($5, 'USD')))
+         RETURNING id, creator_id, name, description, price, currency, created_at, updated_at"
+    )
+    .bind(&claims.sub)
+    .bind(payload.name.trim())
+    .bind(&payload.description)
+    .bind(payload.price)
+    .bind(&payload.currency)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error creating tier: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": tier
+    })))
+}
+
+async fn update_tier(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(payload): Json<MembershipTierRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    validate_tier(&payload)?;
+
+    let tier = sqlx::query_as::<_, MembershipTier>(
+        "UPDATE membership_tiers
+         SET name = $3, description = $4, price = $5, currency = UPPER(COALESCE($6, currency)), updated_at = NOW()
+         WHERE id = $1 AND creator_id = $2
+         RETURNING id, creator_id, name, description, price, currency, created_at, updated_at"
+    )
+    .bind(id)
+    .bind(&claims.sub)
+    .bind(payload.name.trim())
+    .bind(&payload.description)
+    .bind(payload.price)
+    .bind(&payload.currency)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": tier
+    })))
+}
+
+fn validate_tier(payload: &MembershipTierRequest) -> Result<(), StatusCode> {
+    if payload.name.trim().is_empty() || !payload.price.is_finite() || payload.price < 0.0 {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    Ok(())
+}
@@ -6,12 +6,12 @@ use axum::{
     Router,
 };
 use sqlx::Row;
-use uuid::Uuid;
 
 use crate::{
     auth::Claims,
     database::Database,
     models::User,
+    routes::{blocks::user_block_routes, donations::user_donation_routes, follows::user_follow_routes},
 };
 
 pub fn user_routes() -> Router<Database> {
@@ -21,6 +21,9 @@ pub fn user_routes() -> Router<Database> {
         .route("/become-creator", post(become_creator))
         .route("/:id", get(get_user_by_id))
         .route("/:id", put(update_user))
+        .merge(user_donation_routes())
+        .merge(user_follow_routes())
+        .merge(user_block_routes())
 }
 
 async fn get_current_user(
@@ -40,12 +43,12 @@ async fn get_current_user(
 
 async fn get_user_by_id(
     State(db): State<Database>,
-    Path(id): Path<Uuid>,
+    Path(id): Path<String>,
 ) -> Result<Json<User>, StatusCode> {
     let user = sqlx::query_as::<_, User>(
         "SELECT * FROM users WHERE id = $1"
     )
-    .bind(id)
+    .bind(&id)
     .fetch_one(&db.pool)
     .await
     .map_err(|_| StatusCode::NOT_FOUND)?;
@@ -55,12 +58,12 @@ async fn get_user_by_id(
 
 async fn update_user(
     State(db): State<Database>,
-    Path(id): Path<Uuid>,
+    Path(id): Path<String>,
     claims: Claims,
     Json(payload): Json<serde_json::Value>,
 ) -> Result<Json<User>, StatusCode> {
     // Only allow users to update their own profile
-    if claims.sub.parse::<Uuid>().unwrap() != id {
+    if claims.sub != id {
         return Err(StatusCode::FORBIDDEN);
     }
 
@@ -79,7 +82,7 @@ async fn update_user(
         RETURNING *
         "#
     )
-    .bind(id)
+    .bind(&id)
     .bind(display_name)
     .bind(bio)
     .bind(is_creator)
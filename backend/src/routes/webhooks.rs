@@ -0,0 +1,589 @@
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::post,
+    Router,
+};
+use serde_json::Value;
+
+use crate::{
+    config::Config,
+    database::Database,
+    models::{donation_status, purchase_status},
+    payments::{from_minor_units, verify_webhook_signature},
+    routes::{
+        products::PRODUCT_CACHE_PREFIX,
+        refunds::{
+            apply_refunded_state, cancel_refunded_subscription, record_payment_audit, AuditEntry, Charge,
+            RefundTarget,
+        },
+        rewards::release_reward,
+        stretch_goals::unlock_stretch_goals,
+    },
+};
+
+pub fn webhook_routes() -> Router<Database> {
+    Router::new()
+        .route("/stripe", post(stripe_webhook))
+}
+
+async fn stripe_webhook(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<Value>, StatusCode> {
+    let config = Config::from_env().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if config.stripe_webhook_secret.is_empty() {
+        eprintln!("Stripe webhook received but STRIPE_WEBHOOK_SECRET is not set");
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    let signature = headers
+        .get("stripe-signature")
+        .and_then(|value| value.to_str().ok())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    if !verify_webhook_signature(&body, signature, &config.stripe_webhook_secret) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let event: Value = serde_json::from_slice(&body).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let event_type = event["type"].as_str().unwrap_or_default();
+    let object = &event["data"]["object"];
+
+    println!("💳 Stripe webhook: {}", event_type);
+
+    match event_type {
+        "payment_intent.amount_capturable_updated" => authorize_pledge(&db, object).await?,
+        "payment_intent.succeeded" => {
+            complete_purchase(&db, object).await?;
+            complete_donation(&db, object).await?
+        }
+        "payment_intent.payment_failed" => fail_donation(&db, object).await?,
+        "payment_intent.canceled" => release_pledge(&db, object).await?,
+        "invoice.payment_succeeded" => record_subscription_charge(&db, object).await?,
+        "charge.refunded" => reconcile_refund(&db, object).await?,
+        "charge.dispute.created" => open_dispute(&db, object).await?,
+        "charge.dispute.closed" => close_dispute(&db, object).await?,
+        _ => {}
+    }
+
+    Ok(Json(serde_json::json!({ "received": true })))
+}
+
+// Amounts on Stripe objects are in minor units of the object's own currency
+fn minor_to_major(object: &Value, field: &str) -> f64 {
+    from_minor_units(object[field].as_i64().unwrap_or(0), object["currency"].as_str().unwrap_or("usd"))
+}
+
+// Keep the latest paid invoice on the subscription so it can be refunded later
+async fn record_subscription_charge(db: &Database, invoice: &Value) -> Result<(), StatusCode> {
+    let (Some(subscription_id), Some(payment_intent_id)) =
+        (invoice["subscription"].as_str(), invoice["payment_intent"].as_str())
+    else {
+        return Ok(());
+    };
+
+    sqlx::query(
+        "UPDATE subscriptions
+         SET last_payment_intent_id = $2, last_charge_amount = $3, last_charge_currency = UPPER($4),
+             last_charged_at = NOW(), refunded_amount = 0, updated_at = NOW()
+         WHERE stripe_subscription_id = $1"
+    )
+    .bind(subscription_id)
+    .bind(payment_intent_id)
+    .bind(minor_to_major(invoice, "amount_paid"))
+    .bind(invoice["currency"].as_str().unwrap_or("usd"))
+    .execute(&db.pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Error recording subscription charge: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(())
+}
+
+// Settle a product purchase once Stripe confirms the payment. The storefront
+// aggregates count settled purchases, so their cache is dropped on any match.
+async fn complete_purchase(db: &Database, intent: &Value) -> Result<(), StatusCode> {
+    let Some(payment_intent_id) = intent["id"].as_str() else {
+        return Ok(());
+    };
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let completed = sqlx::query_as::<_, (uuid::Uuid, f64)>(
+        "UPDATE purchases SET status = $2
+         WHERE stripe_payment_intent_id = $1 AND status = $3
+         RETURNING id, amount::float8"
+    )
+    .bind(payment_intent_id)
+    .bind(purchase_status::COMPLETED)
+    .bind(purchase_status::PENDING)
+    .fetch_optional(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error completing purchase: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if let Some((purchase_id, amount)) = completed {
+        record_payment_audit(&mut tx, AuditEntry {
+            target: RefundTarget::Purchase.as_str(),
+            target_id: purchase_id,
+            action: "payment_succeeded",
+            from_status: Some(purchase_status::PENDING),
+            to_status: Some(purchase_status::COMPLETED),
+            amount: Some(amount),
+            actor_id: None,
+            details: Some(payment_intent_id.to_string()),
+        })
+        .await?;
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let is_purchase = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM purchases WHERE stripe_payment_intent_id = $1)"
+    )
+    .bind(payment_intent_id)
+    .fetch_one(&db.pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if is_purchase {
+        db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+    }
+
+    Ok(())
+}
+
+// Count a donation towards its campaign once Stripe confirms the payment
+async fn complete_donation(db: &Database, intent: &Value) -> Result<(), StatusCode> {
+    let Some(payment_intent_id) = intent["id"].as_str() else {
+        return Ok(());
+    };
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Only the PENDING -> COMPLETED transition moves money, so redelivered events are no-ops
+    let completed = sqlx::query_as::<_, (uuid::Uuid, uuid::Uuid, f64)>(
+        "UPDATE donations SET status = $2, completed_at = NOW(), updated_at = NOW()
+         WHERE stripe_payment_intent_id = $1 AND status = $3
+         RETURNING id, campaign_id, amount"
+    )
+    .bind(payment_intent_id)
+    .bind(donation_status::COMPLETED)
+    .bind(donation_status::PENDING)
+    .fetch_optional(&mut tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Error completing donation: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let Some((donation_id, campaign_id, amount)) = completed else {
+        // A pledge captured outside the settlement job (e.g. from the Stripe
+        // dashboard) is already counted in the campaign total
+        sqlx::query(
+            "UPDATE donations SET status = $2, completed_at = NOW(), settled_at = NOW(), updated_at = NOW()
+             WHERE stripe_payment_intent_id = $1 AND status = $3"
+        )
+        .bind(payment_intent_id)
+        .bind(donation_status::COMPLETED)
+        .bind(donation_status::AUTHORIZED)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        return Ok(());
+    };
+
+    add_to_campaign_total(&mut tx, campaign_id, amount).await?;
+
+    record_payment_audit(&mut tx, AuditEntry {
+        target: RefundTarget::Donation.as_str(),
+        target_id: donation_id,
+        action: "payment_succeeded",
+        from_status: Some(donation_status::PENDING),
+        to_status: Some(donation_status::COMPLETED),
+        amount: Some(amount),
+        actor_id: None,
+        details: Some(payment_intent_id.to_string()),
+    })
+    .await?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(())
+}
+
+// An all-or-nothing pledge was authorized; it counts towards the goal until settled
+async fn authorize_pledge(db: &Database, intent: &Value) -> Result<(), StatusCode> {
+    let Some(payment_intent_id) = intent["id"].as_str() else {
+        return Ok(());
+    };
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let authorized = sqlx::query_as::<_, (uuid::Uuid, uuid::Uuid, f64)>(
+        "UPDATE donations SET status = $2, updated_at = NOW()
+         WHERE stripe_payment_intent_id = $1 AND status = $3
+         RETURNING id, campaign_id, amount"
+    )
+    .bind(payment_intent_id)
+    .bind(donation_status::AUTHORIZED)
+    .bind(donation_status::PENDING)
+    .fetch_optional(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let Some((donation_id, campaign_id, amount)) = authorized else {
+        return Ok(());
+    };
+
+    add_to_campaign_total(&mut tx, campaign_id, amount).await?;
+
+    record_payment_audit(&mut tx, AuditEntry {
+        target: RefundTarget::Donation.as_str(),
+        target_id: donation_id,
+        action: "pledge_authorized",
+        from_status: Some(donation_status::PENDING),
+        to_status: Some(donation_status::AUTHORIZED),
+        amount: Some(amount),
+        actor_id: None,
+        details: Some(payment_intent_id.to_string()),
+    })
+    .await?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(())
+}
+
+// Stripe cancels uncaptured intents when the authorization expires
+async fn release_pledge(db: &Database, intent: &Value) -> Result<(), StatusCode> {
+    let Some(payment_intent_id) = intent["id"].as_str() else {
+        return Ok(());
+    };
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let released = sqlx::query_as::<_, (uuid::Uuid, uuid::Uuid, f64)>(
+        "UPDATE donations SET status = $2, settled_at = NOW(), updated_at = NOW()
+         WHERE stripe_payment_intent_id = $1 AND status = $3
+         RETURNING id, campaign_id, amount"
+    )
+    .bind(payment_intent_id)
+    .bind(donation_status::RELEASED)
+    .bind(donation_status::AUTHORIZED)
+    .fetch_optional(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let Some((donation_id, campaign_id, amount)) = released else {
+        return Ok(());
+    };
+
+    add_to_campaign_total(&mut tx, campaign_id, -amount).await?;
+    release_reward(&mut tx, donation_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    record_payment_audit(&mut tx, AuditEntry {
+        target: RefundTarget::Donation.as_str(),
+        target_id: donation_id,
+        action: "pledge_released",
+        from_status: Some(donation_status::AUTHORIZED),
+        to_status: Some(donation_status::RELEASED),
+        amount: Some(amount),
+        actor_id: None,
+        details: intent["cancellation_reason"].as_str().map(str::to_string),
+    })
+    .await?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(())
+}
+
+async fn add_to_campaign_total(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    campaign_id: uuid::Uuid,
+    amount: f64,
+) -> Result<(), StatusCode> {
+    sqlx::query(
+        "UPDATE campaigns SET current_amount = GREATEST(COALESCE(current_amount, 0) + $2, 0), updated_at = NOW()
+         WHERE id = $1"
+    )
+    .bind(campaign_id)
+    .bind(amount)
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if amount > 0.0 {
+        unlock_stretch_goals(tx, campaign_id)
+            .await
+            .map_err(|e| {
+                eprintln!("Error unlocking stretch goals: {:?}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+    }
+
+    Ok(())
+}
+
+async fn fail_donation(db: &Database, intent: &Value) -> Result<(), StatusCode> {
+    let Some(payment_intent_id) = intent["id"].as_str() else {
+        return Ok(());
+    };
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let failed = sqlx::query_scalar::<_, uuid::Uuid>(
+        "UPDATE donations SET status = $2, updated_at = NOW()
+         WHERE stripe_payment_intent_id = $1 AND status = $3
+         RETURNING id"
+    )
+    .bind(payment_intent_id)
+    .bind(donation_status::FAILED)
+    .bind(donation_status::PENDING)
+    .fetch_optional(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // The pledge never went through, so its reward goes back on offer
+    if let Some(donation_id) = failed {
+        release_reward(&mut tx, donation_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(())
+}
+
+async fn find_charge(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    payment_intent_id: &str,
+) -> Result<Option<(RefundTarget, Charge)>, StatusCode> {
+    let lookups = [
+        (RefundTarget::Purchase, "SELECT id FROM purchases WHERE stripe_payment_intent_id = $1 LIMIT 1"),
+        (RefundTarget::Subscription, "SELECT id FROM subscriptions WHERE last_payment_intent_id = $1 LIMIT 1"),
+        (RefundTarget::Donation, "SELECT id FROM donations WHERE stripe_payment_intent_id = $1 LIMIT 1"),
+    ];
+
+    for (target, query) in lookups {
+        let id = sqlx::query_scalar::<_, uuid::Uuid>(query)
+            .bind(payment_intent_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        if let Some(id) = id {
+            let charge = sqlx::query_as::<_, Charge>(target.select_for_update_sql())
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            return Ok(Some((target, charge)));
+        }
+    }
+
+    Ok(None)
+}
+
+// Refunds issued from the Stripe dashboard still need to land in our records
+async fn reconcile_refund(db: &Database, stripe_charge: &Value) -> Result<(), StatusCode> {
+    let Some(payment_intent_id) = stripe_charge["payment_intent"].as_str() else {
+        return Ok(());
+    };
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let Some((target, mut charge)) = find_charge(&mut tx, payment_intent_id).await? else {
+        return Ok(());
+    };
+
+    let refunded = minor_to_major(stripe_charge, "amount_refunded");
+    if refunded <= charge.refunded_amount {
+        return Ok(());
+    }
+
+    sqlx::query(target.set_refunded_amount_sql())
+        .bind(charge.id)
+        .bind(refunded)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let difference = refunded - charge.refunded_amount;
+    charge.refunded_amount = refunded;
+    let to_status = apply_refunded_state(&mut tx, target, &charge, difference).await?;
+
+    record_payment_audit(&mut tx, AuditEntry {
+        target: target.as_str(),
+        target_id: charge.id,
+        action: "refund_reconciled",
+        from_status: Some(&charge.status),
+        to_status: Some(&to_status),
+        amount: Some(difference),
+        actor_id: None,
+        details: stripe_charge["id"].as_str().map(str::to_string),
+    })
+    .await?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+
+    if target == RefundTarget::Subscription && charge.is_fully_refunded() {
+        cancel_refunded_subscription(db, charge.id, None).await?;
+    }
+
+    Ok(())
+}
+
+async fn open_dispute(db: &Database, dispute: &Value) -> Result<(), StatusCode> {
+    let (Some(dispute_id), Some(payment_intent_id)) =
+        (dispute["id"].as_str(), dispute["payment_intent"].as_str())
+    else {
+        return Ok(());
+    };
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let Some((target, charge)) = find_charge(&mut tx, payment_intent_id).await? else {
+        return Ok(());
+    };
+
+    let inserted = sqlx::query(
+        "INSERT INTO payment_disputes (stripe_dispute_id, target_type, target_id, amount, reason, status)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (stripe_dispute_id) DO NOTHING"
+    )
+    .bind(dispute_id)
+    .bind(target.as_str())
+    .bind(charge.id)
+    .bind(minor_to_major(dispute, "amount"))
+    .bind(dispute["reason"].as_str())
+    .bind(dispute["status"].as_str().unwrap_or("needs_response"))
+    .execute(&mut tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Stripe retries deliveries; only flag the record once
+    if inserted.rows_affected() == 0 {
+        return Ok(());
+    }
+
+    sqlx::query(&format!("UPDATE {} SET disputed_at = NOW() WHERE id = $1", target.table()))
+        .bind(charge.id)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let to_status = if target.uses_settlement_status()
+        && purchase_status::can_transition(&charge.status, purchase_status::DISPUTED)
+    {
+        sqlx::query(&format!("UPDATE {} SET status = $2 WHERE id = $1", target.table()))
+            .bind(charge.id)
+            .bind(purchase_status::DISPUTED)
+            .execute(&mut tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        purchase_status::DISPUTED.to_string()
+    } else {
+        charge.status.clone()
+    };
+
+    record_payment_audit(&mut tx, AuditEntry {
+        target: target.as_str(),
+        target_id: charge.id,
+        action: "dispute_opened",
+        from_status: Some(&charge.status),
+        to_status: Some(&to_status),
+        amount: Some(minor_to_major(dispute, "amount")),
+        actor_id: None,
+        details: Some(dispute_id.to_string()),
+    })
+    .await?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+
+    Ok(())
+}
+
+async fn close_dispute(db: &Database, dispute: &Value) -> Result<(), StatusCode> {
+    let (Some(dispute_id), Some(payment_intent_id)) =
+        (dispute["id"].as_str(), dispute["payment_intent"].as_str())
+    else {
+        return Ok(());
+    };
+    let outcome = dispute["status"].as_str().unwrap_or_default();
+
+    let mut tx = db.pool.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let Some((target, mut charge)) = find_charge(&mut tx, payment_intent_id).await? else {
+        return Ok(());
+    };
+
+    sqlx::query("UPDATE payment_disputes SET status = $2, updated_at = NOW() WHERE stripe_dispute_id = $1")
+        .bind(dispute_id)
+        .bind(outcome)
+        .execute(&mut tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let to_status = if outcome == "lost" {
+        // A lost dispute is a forced full refund
+        sqlx::query(target.set_refunded_amount_sql())
+            .bind(charge.id)
+            .bind(charge.amount)
+            .execute(&mut tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let difference = charge.amount - charge.refunded_amount;
+        charge.refunded_amount = charge.amount;
+        apply_refunded_state(&mut tx, target, &charge, difference).await?
+    } else if target.uses_settlement_status() && charge.status == purchase_status::DISPUTED {
+        let restored = if charge.refunded_amount > 0.0 {
+            purchase_status::PARTIALLY_REFUNDED
+        } else {
+            purchase_status::COMPLETED
+        };
+        sqlx::query(&format!("UPDATE {} SET status = $2 WHERE id = $1", target.table()))
+            .bind(charge.id)
+            .bind(restored)
+            .execute(&mut tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        restored.to_string()
+    } else {
+        charge.status.clone()
+    };
+
+    record_payment_audit(&mut tx, AuditEntry {
+        target: target.as_str(),
+        target_id: charge.id,
+        action: "dispute_closed",
+        from_status: Some(&charge.status),
+        to_status: Some(&to_status),
+        amount: Some(minor_to_major(dispute, "amount")),
+        actor_id: None,
+        details: Some(format!("{} ({})", dispute_id, outcome)),
+    })
+    .await?;
+
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    db.cache.invalidate_prefix(PRODUCT_CACHE_PREFIX);
+
+    if outcome == "lost" && target == RefundTarget::Subscription && charge.is_fully_refunded() {
+        cancel_refunded_subscription(db, charge.id, None).await?;
+    }
+
+    Ok(())
+}
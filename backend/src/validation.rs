@@ -0,0 +1,87 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+
+/// Validation messages keyed by the request field they refer to
+#[derive(Debug, Default)]
+pub struct FieldErrors(BTreeMap<&'static str, String>);
+
+impl FieldErrors {
+    pub fn add(&mut self, field: &'static str, message: impl Into<String>) {
+        self.0.entry(field).or_insert_with(|| message.into());
+    }
+
+    /// `Err` with every collected message, if there are any
+    pub fn into_result(self) -> Result<(), RequestError> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(RequestError::Invalid(self))
+        }
+    }
+}
+
+/// Handler error that can carry field-level validation messages. Bare status
+/// codes convert into it, so existing helpers still work with `?`.
+#[derive(Debug)]
+pub enum RequestError {
+    Status(StatusCode),
+    Invalid(FieldErrors),
+}
+
+impl From<StatusCode> for RequestError {
+    fn from(status: StatusCode) -> Self {
+        RequestError::Status(status)
+    }
+}
+
+impl IntoResponse for RequestError {
+    fn into_response(self) -> Response {
+        match self {
+            RequestError::Status(status) => status.into_response(),
+            RequestError::Invalid(errors) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(serde_json::json!({
+                    "success": false,
+                    "error": "Validation failed",
+                    "errors": errors.0
+                })),
+            )
+                .into_response(),
+        }
+    }
+}
+
+/// An absolute `http` or `https` URL with a host
+pub fn is_http_url(value: &str) -> bool {
+    let rest = value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"));
+
+    match rest {
+        Some(rest) => {
+            let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+            !host.is_empty() && !value.chars().any(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// A three-letter ISO 4217 currency code such as `USD`, in either case
+pub fn is_currency_code(value: &str) -> bool {
+    value.len() == 3 && value.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// `deserialize_with` for `Option<Option<T>>` update fields, paired with
+/// `#[serde(default)]`: an absent field stays `None`, an explicit `null`
+/// becomes `Some(None)` so the column can be cleared.
+pub fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}